@@ -0,0 +1,75 @@
+use super::state::{score_guess, LetterStatus};
+
+/// Picks the feedback that keeps the most words in play, in the spirit of Absurdle.
+///
+/// The candidates are split up by the colours the guess would get against each of them, and the
+/// biggest group survives. Between groups of the same size, the one that gives away the least is
+/// kept, so a guess only comes back all green once it's the last word left. There has to be at
+/// least one candidate, which a game that started with some never runs out of.
+pub fn dodge(guess: &str, candidates: &[String]) -> (Vec<LetterStatus>, Vec<String>) {
+    let mut buckets: Vec<(Vec<LetterStatus>, Vec<String>)> = Vec::new();
+
+    for candidate in candidates {
+        let colors = score_guess(guess, candidate);
+
+        match buckets.iter_mut().find(|(pattern, _)| *pattern == colors) {
+            Some((_, words)) => words.push(candidate.clone()),
+            None => buckets.push((colors, vec![candidate.clone()])),
+        }
+    }
+
+    // `max_by_key` keeps the last of equal elements, so go through them backwards to keep the
+    // first one found instead
+    buckets
+        .into_iter()
+        .rev()
+        .max_by_key(|(pattern, words)| {
+            let greens = pattern
+                .iter()
+                .filter(|c| **c == LetterStatus::Correct)
+                .count();
+            let yellows = pattern
+                .iter()
+                .filter(|c| **c == LetterStatus::Present)
+                .count();
+
+            (words.len(), usize::MAX - greens, usize::MAX - yellows)
+        })
+        .expect("an Absurdle game always has a word left to dodge with")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use LetterStatus::{Absent as A, Correct as C, Present as P};
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn the_biggest_bucket_survives() {
+        let candidates = words(&["crane", "brine", "those", "whose", "geese"]);
+
+        let (pattern, remaining) = dodge("those", &candidates);
+
+        // crane and brine both share nothing with "those" apart from a green "e"
+        assert_eq!(pattern, vec![A, A, A, A, C]);
+        assert_eq!(remaining, words(&["crane", "brine"]));
+    }
+
+    #[test]
+    fn ties_give_away_as_little_as_possible() {
+        let (pattern, remaining) = dodge("crane", &words(&["crane", "those"]));
+        assert_eq!(pattern, vec![A, A, A, A, C]);
+        assert_eq!(remaining, words(&["those"]));
+
+        let (pattern, remaining) = dodge("those", &words(&["those"]));
+        assert_eq!(pattern, vec![C, C, C, C, C]);
+        assert_eq!(remaining, words(&["those"]));
+
+        let (pattern, _) = dodge("ethos", &words(&["those", "tacit"]));
+        assert_eq!(pattern, vec![A, P, A, A, A]);
+    }
+}
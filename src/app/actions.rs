@@ -11,16 +11,30 @@ pub enum Action {
     Edit,
     Backspace,
     Submit,
+    Resume,
+    NewGame,
+    SelectPrevious,
+    SelectNext,
+    StepForward,
+    StepBack,
+    SetWord,
 }
 
 impl Action {
     /// All available actions
     pub fn iterator() -> Iter<'static, Action> {
-        static ACTIONS: [Action; 4] = [
+        static ACTIONS: [Action; 11] = [
             Action::Quit,
             Action::Edit,
             Action::Backspace,
             Action::Submit,
+            Action::Resume,
+            Action::NewGame,
+            Action::SelectPrevious,
+            Action::SelectNext,
+            Action::StepForward,
+            Action::StepBack,
+            Action::SetWord,
         ];
         ACTIONS.iter()
     }
@@ -31,6 +45,13 @@ impl Action {
             Action::Quit => &[Key::Ctrl('c'), Key::Esc],
             Action::Submit => &[Key::Enter],
             Action::Backspace => &[Key::Backspace, Key::Delete],
+            Action::Resume => &[Key::Char('y'), Key::Enter],
+            Action::NewGame => &[Key::Char('n')],
+            Action::SelectPrevious => &[Key::Up],
+            Action::SelectNext => &[Key::Down],
+            Action::StepForward => &[Key::Right, Key::Enter],
+            Action::StepBack => &[Key::Left],
+            Action::SetWord => &[Key::Enter],
             Action::Edit => &[
                 Key::Char('a'),
                 Key::Char('b'),
@@ -71,6 +92,13 @@ impl Display for Action {
             Action::Submit => "Submit Guess",
             Action::Backspace => "Delete",
             Action::Edit => "",
+            Action::Resume => "Resume Game",
+            Action::NewGame => "New Game",
+            Action::SelectPrevious => "Previous Game",
+            Action::SelectNext => "Next Game",
+            Action::StepForward => "Next Row",
+            Action::StepBack => "Previous Row",
+            Action::SetWord => "Set Word",
         };
         write!(f, "{}", str)
     }
@@ -81,9 +109,10 @@ impl Display for Action {
 pub struct Actions(Vec<Action>);
 
 impl Actions {
-    /// Given a key, find the corresponding action
+    /// Given a key, find the corresponding action. Only the actions in this context are
+    /// looked at, so the same key can mean different things in different contexts.
     pub fn find(&self, key: Key) -> Option<&Action> {
-        Action::iterator().find(|action| action.keys().contains(&key))
+        self.0.iter().find(|action| action.keys().contains(&key))
     }
 
     /// Get contextual actions.
@@ -0,0 +1,271 @@
+use std::fmt::{self, Display};
+
+use super::words::{MAX_WORD_LENGTH, MIN_WORD_LENGTH};
+
+/// The version of the code format, so codes made by a newer build can be told apart from
+/// mistyped ones
+const CODE_VERSION: u8 = 1;
+
+/// Crockford's base 32, which leaves out letters that are easy to mix up with digits
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Where the scrambling starts, spelling "Rust"
+const KEY: u32 = 0x5275_7374;
+
+const HARD_MODE: u8 = 1 << 3;
+const UNLIMITED: u8 = 1 << 4;
+
+/// A puzzle someone made for a word of their choosing, to be shared as a short code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleCode {
+    pub word: String,
+    pub hard_mode: bool,
+    /// Whether the puzzle is played with unlimited guesses
+    pub unlimited: bool,
+}
+
+/// Why a code couldn't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    /// Something other than the letters and digits a code is made of
+    NotACode(char),
+    /// The code was mistyped, cut short or changed by hand
+    Tampered,
+    /// Made by a newer version of Rustle
+    NewerVersion,
+}
+
+impl Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeError::NotACode(character) => {
+                write!(f, "puzzle codes can't have '{}' in them", character)
+            }
+            CodeError::Tampered => write!(f, "this puzzle code has been mistyped or changed"),
+            CodeError::NewerVersion => {
+                write!(f, "this puzzle code was made by a newer version of Rustle")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+impl PuzzleCode {
+    /// The code for the puzzle. The word is scrambled so it can't be read off the code at a
+    /// glance, though this is no secret to anyone who goes looking.
+    ///
+    /// The word has to be between `MIN_WORD_LENGTH` and `MAX_WORD_LENGTH` lowercase letters.
+    pub fn encode(&self) -> String {
+        let mut flags = (CODE_VERSION << 5) | (self.word.len() - MIN_WORD_LENGTH) as u8;
+        if self.hard_mode {
+            flags |= HARD_MODE;
+        }
+        if self.unlimited {
+            flags |= UNLIMITED;
+        }
+
+        let mut puzzle = vec![flags];
+        puzzle.extend(self.word.bytes().map(|letter| letter - b'a'));
+        let checksum = checksum(&puzzle);
+
+        // The checksum goes first and decides how the rest is scrambled, so changing a letter
+        // changes the whole code
+        let mut bytes = checksum.to_be_bytes().to_vec();
+        scramble(&mut bytes, KEY);
+        scramble(&mut puzzle, KEY ^ u32::from(checksum));
+        bytes.extend(puzzle);
+
+        to_base32(&bytes)
+    }
+
+    /// Reads a code, ignoring case and any dashes or spaces in it. Whether the word is in the
+    /// word list is up to the caller.
+    pub fn decode(code: &str) -> Result<PuzzleCode, CodeError> {
+        let mut bytes = from_base32(code)?;
+        if bytes.len() < MIN_WORD_LENGTH + 3 {
+            return Err(CodeError::Tampered);
+        }
+
+        let (expected, puzzle) = bytes.split_at_mut(2);
+        scramble(expected, KEY);
+        let expected = u16::from_be_bytes([expected[0], expected[1]]);
+        scramble(puzzle, KEY ^ u32::from(expected));
+        if checksum(puzzle) != expected {
+            return Err(CodeError::Tampered);
+        }
+
+        let flags = puzzle[0];
+        if flags >> 5 > CODE_VERSION {
+            return Err(CodeError::NewerVersion);
+        }
+        let letters = &puzzle[1..];
+        let word_length = usize::from(flags & 0b111) + MIN_WORD_LENGTH;
+        if letters.len() != word_length
+            || word_length > MAX_WORD_LENGTH
+            || letters.iter().any(|letter| *letter >= 26)
+        {
+            return Err(CodeError::Tampered);
+        }
+
+        Ok(PuzzleCode {
+            word: letters
+                .iter()
+                .map(|letter| char::from(b'a' + letter))
+                .collect(),
+            hard_mode: flags & HARD_MODE != 0,
+            unlimited: flags & UNLIMITED != 0,
+        })
+    }
+}
+
+/// FNV-1a, folded down to 16 bits. Enough to catch typos and hand edits, not to stop anyone
+/// determined.
+fn checksum(bytes: &[u8]) -> u16 {
+    let hash = bytes.iter().fold(0x811c9dc5u32, |hash, byte| {
+        (hash ^ u32::from(*byte)).wrapping_mul(0x01000193)
+    });
+
+    ((hash >> 16) ^ (hash & 0xffff)) as u16
+}
+
+/// XORs the bytes with an xorshift stream starting from `seed`, which mustn't be zero. Doing it
+/// twice gives the bytes back.
+fn scramble(bytes: &mut [u8], seed: u32) {
+    let mut state = seed;
+    for byte in bytes {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        *byte ^= state as u8;
+    }
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+    let mut code = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            code.push(char::from(ALPHABET[(buffer >> bits) as usize & 31]));
+        }
+    }
+    if bits > 0 {
+        code.push(char::from(ALPHABET[(buffer << (5 - bits)) as usize & 31]));
+    }
+
+    code
+}
+
+fn from_base32(code: &str) -> Result<Vec<u8>, CodeError> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for character in code.chars().filter(|c| *c != '-' && !c.is_whitespace()) {
+        // Letters that look like digits are read as those digits
+        let value = match character.to_ascii_uppercase() {
+            'O' => 0,
+            'I' | 'L' => 1,
+            upper => ALPHABET
+                .iter()
+                .position(|letter| char::from(*letter) == upper)
+                .ok_or(CodeError::NotACode(character))? as u32,
+        };
+
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    // Encoding only ever pads the last character with zeros
+    if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+        return Err(CodeError::Tampered);
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle(word: &str) -> PuzzleCode {
+        PuzzleCode {
+            word: word.to_string(),
+            hard_mode: false,
+            unlimited: false,
+        }
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for puzzle in [
+            puzzle("those"),
+            puzzle("rust"),
+            PuzzleCode {
+                hard_mode: true,
+                ..puzzle("typewriters")
+            },
+            PuzzleCode {
+                unlimited: true,
+                ..puzzle("quizzed")
+            },
+        ] {
+            assert_eq!(PuzzleCode::decode(&puzzle.encode()), Ok(puzzle));
+        }
+    }
+
+    #[test]
+    fn codes_hide_the_word() {
+        let code = puzzle("those").encode();
+
+        assert_eq!(code.len(), 13);
+        assert!(!code.to_lowercase().contains("those"));
+        assert_ne!(code, puzzle("geese").encode());
+    }
+
+    #[test]
+    fn codes_can_be_typed_sloppily() {
+        let code = puzzle("those").encode();
+        let sloppy = format!(
+            "{}-{} ",
+            &code[..6].to_lowercase().replace('1', "l"),
+            &code[6..].replace('0', "O")
+        );
+
+        assert_eq!(PuzzleCode::decode(&sloppy), Ok(puzzle("those")));
+    }
+
+    #[test]
+    fn tampered_codes_are_rejected() {
+        let code = puzzle("those").encode();
+
+        for i in 0..code.len() {
+            let mut tampered = code.clone().into_bytes();
+            tampered[i] = if tampered[i] == b'Z' { b'Y' } else { b'Z' };
+            let tampered = String::from_utf8(tampered).unwrap();
+
+            assert_eq!(
+                PuzzleCode::decode(&tampered),
+                Err(CodeError::Tampered),
+                "{}",
+                tampered
+            );
+        }
+
+        assert_eq!(
+            PuzzleCode::decode(&code[..code.len() - 2]),
+            Err(CodeError::Tampered)
+        );
+        assert_eq!(PuzzleCode::decode(""), Err(CodeError::Tampered));
+        assert_eq!(PuzzleCode::decode("THOSE!"), Err(CodeError::NotACode('!')));
+    }
+}
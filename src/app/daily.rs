@@ -0,0 +1,86 @@
+use chrono::{Local, NaiveDate, NaiveDateTime};
+
+/// Wordle's first puzzle went out on this day, so we count ours from it too
+pub const FIRST_PUZZLE_DATE: NaiveDate = match NaiveDate::from_ymd_opt(2021, 6, 19) {
+    Some(date) => date,
+    None => panic!("invalid first puzzle date"),
+};
+
+/// Where the time of day comes from. The daily puzzle rolls over at local midnight, and swapping
+/// the clock lets that be tested without waiting for it.
+pub trait Clock: Send + Sync {
+    /// The current local date and time
+    fn now(&self) -> NaiveDateTime;
+
+    /// The current local date
+    fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+}
+
+/// The clock on the wall, in the local timezone
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+/// A clock stuck at a given moment
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub NaiveDateTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> NaiveDateTime {
+        self.0
+    }
+}
+
+/// The puzzle number for a date, or `None` for days before the first puzzle
+pub fn puzzle_number(date: NaiveDate) -> Option<u32> {
+    let days = date.signed_duration_since(FIRST_PUZZLE_DATE).num_days();
+
+    u32::try_from(days).ok()
+}
+
+/// The date a puzzle number was (or will be) played on
+pub fn puzzle_date(number: u32) -> NaiveDate {
+    FIRST_PUZZLE_DATE + chrono::Duration::days(i64::from(number))
+}
+
+/// Today's puzzle number according to the given clock
+pub fn todays_puzzle(clock: &dyn Clock) -> u32 {
+    // The system clock would have to be set before June 2021 for this to fall back
+    puzzle_number(clock.today()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> FixedClock {
+        let moment = format!("{} {}", date, time);
+
+        FixedClock(NaiveDateTime::parse_from_str(&moment, "%Y-%m-%d %H:%M:%S").unwrap())
+    }
+
+    #[test]
+    fn puzzles_are_numbered_from_the_first_day() {
+        assert_eq!(puzzle_number(FIRST_PUZZLE_DATE), Some(0));
+        assert_eq!(puzzle_number(puzzle_date(412)), Some(412));
+        assert_eq!(puzzle_date(1).to_string(), "2021-06-20");
+        assert_eq!(puzzle_number(FIRST_PUZZLE_DATE.pred_opt().unwrap()), None);
+    }
+
+    #[test]
+    fn puzzle_rolls_over_at_local_midnight() {
+        let before = todays_puzzle(&at("2022-08-04", "23:59:59"));
+        let after = todays_puzzle(&at("2022-08-05", "00:00:00"));
+
+        assert_eq!(before, 411);
+        assert_eq!(after, 412);
+        assert_eq!(todays_puzzle(&at("2022-08-05", "23:59:59")), 412);
+    }
+}
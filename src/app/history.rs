@@ -0,0 +1,430 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::options::{GameOptions, TimeLimit};
+use super::save::SavedBoard;
+use super::state::{AppState, GameStatus, Stopwatch};
+
+/// A finished game, as it's kept in the history file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the game was over, in local time
+    pub finished: NaiveDateTime,
+    /// How the game was played, like "Daily, Hard" or "Code, Speedrun"
+    pub mode: String,
+    pub puzzle_number: Option<u32>,
+    pub word_length: usize,
+    pub max_attempts: Option<usize>,
+    /// The solution of each board, with the colours every guess got on it
+    pub boards: Vec<SavedBoard>,
+    pub guesses: Vec<String>,
+    pub won: bool,
+    /// How long the game took, in milliseconds
+    pub elapsed_ms: u64,
+}
+
+impl HistoryEntry {
+    /// The entry for a game that's over, or `None` while it's still going. The options and
+    /// whether it was played head to head are what tell the kinds of game apart.
+    pub fn from_state(
+        state: &AppState,
+        options: &GameOptions,
+        versus: bool,
+        finished: NaiveDateTime,
+    ) -> Option<HistoryEntry> {
+        if state.game_status == GameStatus::InProgress {
+            return None;
+        }
+
+        Some(HistoryEntry {
+            finished,
+            mode: mode(state, options, versus),
+            puzzle_number: state.puzzle_number,
+            word_length: state.word_length,
+            max_attempts: state.max_attempts,
+            boards: state.boards.iter().map(SavedBoard::from_board).collect(),
+            guesses: state.guesses.clone(),
+            won: state.game_status == GameStatus::Win,
+            elapsed_ms: state.stopwatch.elapsed().as_millis() as u64,
+        })
+    }
+
+    pub fn solutions(&self) -> Vec<&str> {
+        self.boards
+            .iter()
+            .map(|board| board.solution.as_str())
+            .collect()
+    }
+
+    /// The game as it stood after its first `rows` guesses, for drawing like any other game
+    pub fn replay(&self, rows: usize, color_blind: bool) -> AppState {
+        let guesses = &self.guesses[..rows.min(self.guesses.len())];
+        let mut stopwatch = Stopwatch::starting_from(Duration::from_millis(self.elapsed_ms));
+        stopwatch.stop();
+
+        AppState {
+            boards: self
+                .boards
+                .iter()
+                .map(|board| board.restore(guesses))
+                .collect(),
+            guesses: guesses.to_vec(),
+            attempt: guesses.len(),
+            // The grid stays the same size the whole way through, even for unlimited games
+            max_attempts: Some(self.max_attempts.unwrap_or(self.guesses.len())),
+            word_length: self.word_length,
+            color_blind,
+            puzzle_number: self.puzzle_number,
+            stopwatch,
+            ..AppState::default()
+        }
+    }
+}
+
+/// Adds a game to the end of the history file, which has a line of JSON per game
+pub fn append(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())?;
+
+    Ok(())
+}
+
+/// Reads every game in the history file, oldest first. Lines that can't be read, like one cut
+/// short by a crash, are skipped rather than losing the rest.
+pub fn load(path: &Path) -> Result<Vec<HistoryEntry>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn mode(state: &AppState, options: &GameOptions, versus: bool) -> String {
+    let mut parts = vec![String::from(if state.candidates.is_some() {
+        "Absurdle"
+    } else if state.puzzle_number.is_some() {
+        "Daily"
+    } else if versus {
+        "Versus"
+    } else if options.hot_seat {
+        "Hot seat"
+    } else if options.word.is_some() {
+        "Code"
+    } else {
+        "Random"
+    })];
+    if state.boards.len() > 1 {
+        parts.push(format!("{} boards", state.boards.len()));
+    }
+    if state.hard_mode {
+        parts.push(String::from("Hard"));
+    }
+    match options.time_limit {
+        Some(TimeLimit::Speedrun(_)) => parts.push(String::from("Speedrun")),
+        Some(TimeLimit::Countdown(_)) => parts.push(String::from("Countdown")),
+        None => {}
+    }
+    if options.survival.is_some() {
+        parts.push(String::from("Survival"));
+    }
+
+    parts.join(", ")
+}
+
+/// Where the player is in the history screen: picking a game from the list, or stepping through
+/// one a row at a time
+#[derive(Debug, Clone, Default)]
+pub struct HistoryViewer {
+    /// Every game played, newest first
+    pub entries: Vec<HistoryEntry>,
+    pub selected: usize,
+    /// How many rows of the selected game are showing, once it's been opened
+    pub replaying: Option<usize>,
+}
+
+impl HistoryViewer {
+    pub fn new(mut entries: Vec<HistoryEntry>) -> HistoryViewer {
+        entries.reverse();
+
+        HistoryViewer {
+            entries,
+            ..HistoryViewer::default()
+        }
+    }
+
+    pub fn selected_entry(&self) -> Option<&HistoryEntry> {
+        self.entries.get(self.selected)
+    }
+
+    pub fn select_previous(&mut self) {
+        if self.replaying.is_none() {
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.replaying.is_none() && self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Opens the selected game with just its first row showing, or shows the next row of the
+    /// one that's open
+    pub fn step_forward(&mut self) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+
+        let rows = entry.guesses.len();
+        self.replaying = Some(match self.replaying {
+            Some(shown) => (shown + 1).min(rows),
+            None => 1.min(rows),
+        });
+    }
+
+    pub fn step_back(&mut self) {
+        if let Some(shown) = &mut self.replaying {
+            *shown = shown.saturating_sub(1);
+        }
+    }
+
+    /// Goes back to the list
+    pub fn close(&mut self) {
+        self.replaying = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn finished_game(solution: &str, guesses: &[&str]) -> AppState {
+        let mut state = AppState::with_solution(solution);
+        for guess in guesses {
+            state.guesses.push(guess.to_string());
+            state.check_word_status();
+            state.increment_attempt();
+        }
+        if state.all_solved() {
+            state.game_won();
+        } else {
+            state.game_lost();
+        }
+
+        state
+    }
+
+    fn noon() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2022, 8, 4)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn games_are_appended_and_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let first = HistoryEntry::from_state(
+            &finished_game("those", &["crane", "those"]),
+            &GameOptions::default(),
+            false,
+            noon(),
+        )
+        .unwrap();
+        let second = HistoryEntry::from_state(
+            &finished_game("geese", &["crane"]),
+            &GameOptions::default(),
+            false,
+            noon(),
+        )
+        .unwrap();
+
+        append(&path, &first).unwrap();
+        append(&path, &second).unwrap();
+        // A line cut short doesn't take the others down with it
+        fs::write(
+            &path,
+            fs::read_to_string(&path).unwrap() + "{\"finished\":\n",
+        )
+        .unwrap();
+        append(&path, &first).unwrap();
+
+        assert_eq!(load(&path).unwrap(), vec![first.clone(), second, first]);
+        assert_eq!(load(&dir.path().join("missing.jsonl")).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn entries_describe_the_game() {
+        let entry = HistoryEntry::from_state(
+            &finished_game("those", &["crane", "those"]),
+            &GameOptions::default(),
+            false,
+            noon(),
+        )
+        .unwrap();
+
+        assert_eq!(entry.mode, "Random");
+        assert_eq!(entry.solutions(), vec!["those"]);
+        assert!(entry.won);
+        assert_eq!(entry.boards[0].rows, vec!["----G", "GGGGG"]);
+
+        assert_eq!(
+            HistoryEntry::from_state(
+                &AppState::with_solution("those"),
+                &GameOptions::default(),
+                false,
+                noon()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn entries_say_what_kind_of_game_it_was() {
+        let minutes = Duration::from_secs(300);
+        for (options, versus, expected) in [
+            (GameOptions::default(), false, "Random"),
+            (GameOptions::default(), true, "Versus"),
+            (
+                GameOptions {
+                    puzzle: Some(411),
+                    ..GameOptions::default()
+                },
+                false,
+                "Daily",
+            ),
+            (
+                GameOptions {
+                    absurdle: true,
+                    max_attempts: None,
+                    ..GameOptions::default()
+                },
+                false,
+                "Absurdle",
+            ),
+            (
+                GameOptions {
+                    word: Some(String::from("those")),
+                    hard_mode: true,
+                    ..GameOptions::default()
+                },
+                false,
+                "Code, Hard",
+            ),
+            (
+                GameOptions {
+                    hot_seat: true,
+                    ..GameOptions::default()
+                },
+                false,
+                "Hot seat",
+            ),
+            (
+                GameOptions {
+                    boards: 2,
+                    time_limit: Some(TimeLimit::Countdown(minutes)),
+                    ..GameOptions::default()
+                },
+                false,
+                "Random, 2 boards, Countdown",
+            ),
+            (
+                GameOptions {
+                    time_limit: Some(TimeLimit::Speedrun(minutes)),
+                    ..GameOptions::default()
+                },
+                false,
+                "Random, Speedrun",
+            ),
+            (
+                GameOptions {
+                    survival: Some(7),
+                    ..GameOptions::default()
+                },
+                false,
+                "Random, Survival",
+            ),
+        ] {
+            let mut state = AppState::new(&options);
+            state.game_lost();
+
+            let entry = HistoryEntry::from_state(&state, &options, versus, noon()).unwrap();
+            assert_eq!(entry.mode, expected, "{:?}", options);
+        }
+    }
+
+    #[test]
+    fn replays_show_the_original_colours_a_row_at_a_time() {
+        let state = finished_game("those", &["crane", "geese", "those"]);
+        let entry =
+            HistoryEntry::from_state(&state, &GameOptions::default(), false, noon()).unwrap();
+
+        let replay = entry.replay(2, false);
+
+        assert_eq!(replay.guesses, vec!["crane", "geese"]);
+        assert_eq!(
+            replay.boards[0].square_statuses,
+            state.boards[0].square_statuses[..2]
+        );
+        assert!(!replay.all_solved());
+        assert!(entry.replay(3, false).all_solved());
+    }
+
+    #[test]
+    fn the_viewer_steps_through_games() {
+        let entries = ["those", "geese"]
+            .iter()
+            .map(|solution| {
+                HistoryEntry::from_state(
+                    &finished_game(solution, &["crane", solution]),
+                    &GameOptions::default(),
+                    false,
+                    noon(),
+                )
+                .unwrap()
+            })
+            .collect();
+        let mut viewer = HistoryViewer::new(entries);
+
+        // The newest game comes first
+        assert_eq!(viewer.selected_entry().unwrap().solutions(), vec!["geese"]);
+        viewer.select_next();
+        viewer.select_next();
+        assert_eq!(viewer.selected, 1);
+
+        viewer.step_forward();
+        viewer.select_previous();
+        assert_eq!(viewer.selected, 1);
+        viewer.step_forward();
+        viewer.step_forward();
+        assert_eq!(viewer.replaying, Some(2));
+        viewer.step_back();
+        assert_eq!(viewer.replaying, Some(1));
+
+        viewer.close();
+        viewer.select_previous();
+        assert_eq!((viewer.selected, viewer.replaying), (0, None));
+    }
+}
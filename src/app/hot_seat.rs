@@ -0,0 +1,129 @@
+/// One of the two people sharing the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    pub fn other(self) -> Player {
+        match self {
+            Player::One => Player::Two,
+            Player::Two => Player::One,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Player::One => 0,
+            Player::Two => 1,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Player::One => "Player 1",
+            Player::Two => "Player 2",
+        }
+    }
+}
+
+/// Two players taking turns at the same keyboard, one typing a secret word and the other
+/// guessing it
+#[derive(Debug, Clone)]
+pub struct HotSeat {
+    /// Who picks the word this round
+    pub setter: Player,
+    /// Whether the setter is still typing the word, before the guessing starts
+    pub choosing: bool,
+    pub round: u32,
+    /// Points for player one and player two
+    pub scores: [u32; 2],
+}
+
+impl Default for HotSeat {
+    fn default() -> Self {
+        HotSeat {
+            setter: Player::One,
+            choosing: true,
+            round: 1,
+            scores: [0, 0],
+        }
+    }
+}
+
+impl HotSeat {
+    pub fn guesser(&self) -> Player {
+        self.setter.other()
+    }
+
+    pub fn score(&self, player: Player) -> u32 {
+        self.scores[player.index()]
+    }
+
+    /// The guesser gets a point for solving the word, and the setter gets one for stumping them
+    pub fn round_over(&mut self, solved: bool) {
+        let winner = if solved { self.guesser() } else { self.setter };
+        self.scores[winner.index()] += 1;
+    }
+
+    /// Swaps who picks the word and who guesses it
+    pub fn next_round(&mut self) {
+        self.setter = self.setter.other();
+        self.choosing = true;
+        self.round += 1;
+    }
+
+    /// The running score for the title bar
+    pub fn describe_score(&self) -> String {
+        format!(
+            "Round {} - {}: {}, {}: {}",
+            self.round,
+            Player::One.name(),
+            self.score(Player::One),
+            Player::Two.name(),
+            self.score(Player::Two)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_alternate_between_rounds() {
+        let mut hot_seat = HotSeat::default();
+        assert_eq!(
+            (hot_seat.setter, hot_seat.guesser()),
+            (Player::One, Player::Two)
+        );
+
+        hot_seat.choosing = false;
+        hot_seat.next_round();
+
+        assert_eq!(
+            (hot_seat.setter, hot_seat.guesser()),
+            (Player::Two, Player::One)
+        );
+        assert!(hot_seat.choosing);
+        assert_eq!(hot_seat.round, 2);
+    }
+
+    #[test]
+    fn the_round_goes_to_whoever_got_the_better_of_the_other() {
+        let mut hot_seat = HotSeat::default();
+
+        hot_seat.round_over(true);
+        assert_eq!(hot_seat.scores, [0, 1]);
+        hot_seat.round_over(false);
+        assert_eq!(hot_seat.scores, [1, 1]);
+
+        hot_seat.next_round();
+        hot_seat.round_over(true);
+        assert_eq!(
+            hot_seat.describe_score(),
+            "Round 2 - Player 1: 2, Player 2: 1"
+        );
+    }
+}
@@ -1,12 +1,40 @@
 use self::actions::Actions;
-use self::state::AppState;
+use std::sync::Arc;
+use std::time::Duration;
+
+use self::daily::{Clock, SystemClock};
+use self::history::{HistoryEntry, HistoryViewer};
+use self::hot_seat::HotSeat;
+use self::options::{GameOptions, TimeLimit};
+use self::save::SavedGame;
+use self::solver::{GuessAnalysis, Hints, Solver, Suggestion};
+use self::speedrun::Speedrun;
+use self::state::{AppState, Board, GameStatus, LetterStatus, Stopwatch};
+use self::stats::{Stats, DISTRIBUTION_LEN};
+use self::survival::Survival;
+use self::theme::Theme;
+use self::versus::{Connection, PlayerStatus, Role, Versus};
 use crate::app::actions::Action;
 use crate::inputs::key::Key;
 use crate::io::IoEvent;
 
+pub mod absurdle;
 pub mod actions;
+pub mod code;
+pub mod daily;
+pub mod history;
+pub mod hot_seat;
+pub mod options;
+pub mod save;
+pub mod share;
+pub mod solver;
+pub mod speedrun;
 pub mod state;
+pub mod stats;
+pub mod survival;
+pub mod theme;
 pub mod ui;
+pub mod versus;
 pub mod words;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -21,57 +49,210 @@ pub struct App {
     io_tx: tokio::sync::mpsc::Sender<IoEvent>,
     /// Contextual actions
     actions: Actions,
-    /// State
-    is_loading: bool,
+    /// How many IO events haven't been handled yet
+    loading: usize,
+    /// Rules every new game is started with
+    options: GameOptions,
     state: AppState,
+    /// Stats from every finished game, including this one once it's over
+    stats: Stats,
+    /// Which screen is showing once the game is over
+    end_screen: EndScreen,
+    solver: Arc<Solver>,
+    /// What the hint panel shows, kept up to date after every guess
+    hints: Option<Hints>,
+    /// Whether `hints` is about an earlier guess than the latest
+    hints_pending: bool,
+    /// How each guess compares with the solver's, worked out once the game is over
+    analysis: Option<Vec<GuessAnalysis>>,
+    /// A game left unfinished last time, while the player decides whether to carry on with it
+    resume: Option<SavedGame>,
+    /// The past games screen, which takes the place of a game when it's open
+    history: Option<HistoryViewer>,
+    /// The other player, in a head-to-head game
+    versus: Option<Versus>,
+    /// Whose turn it is and the score so far, when two players share the keyboard
+    hot_seat: Option<HotSeat>,
+    /// The words solved so far and the clock they're racing, in a speedrun
+    speedrun: Option<Speedrun>,
+    /// The guesses left for the rest of the run, in survival
+    survival: Option<Survival>,
+}
+
+/// The screens Enter cycles through once the game is over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndScreen {
+    Result,
+    Stats,
+    Analysis,
+}
+
+impl EndScreen {
+    fn next(self) -> EndScreen {
+        match self {
+            EndScreen::Result => EndScreen::Stats,
+            EndScreen::Stats => EndScreen::Analysis,
+            EndScreen::Analysis => EndScreen::Result,
+        }
+    }
 }
 
+/// How many suggestions the hint panel lists
+const HINT_COUNT: usize = 8;
+
 impl App {
-    pub fn new(io_tx: tokio::sync::mpsc::Sender<IoEvent>) -> Self {
+    pub fn new(io_tx: tokio::sync::mpsc::Sender<IoEvent>, options: GameOptions) -> Self {
         let actions = vec![Action::Quit].into();
-        let is_loading = false;
-        let state = AppState::default();
+        let state = AppState::new(&options);
+        let solver = Arc::new(Solver::new(&options.words));
+        let hot_seat = options.hot_seat.then(HotSeat::default);
 
         Self {
             io_tx,
             actions,
-            is_loading,
+            loading: 0,
+            options,
             state,
+            stats: Stats::default(),
+            end_screen: EndScreen::Result,
+            solver,
+            hints: None,
+            hints_pending: false,
+            analysis: None,
+            resume: None,
+            history: None,
+            versus: None,
+            hot_seat,
+            speedrun: None,
+            survival: None,
         }
     }
 
     /// Handle a user action
     pub async fn do_action(&mut self, key: Key) -> AppReturn {
+        if let Some(viewer) = &mut self.history {
+            return match self.actions.find(key) {
+                Some(Action::Quit) => AppReturn::Exit,
+                Some(Action::SelectPrevious) => {
+                    viewer.select_previous();
+                    AppReturn::Continue
+                }
+                Some(Action::SelectNext) => {
+                    viewer.select_next();
+                    AppReturn::Continue
+                }
+                Some(Action::StepForward) => {
+                    viewer.step_forward();
+                    AppReturn::Continue
+                }
+                Some(Action::StepBack) => {
+                    viewer.step_back();
+                    AppReturn::Continue
+                }
+                Some(Action::Backspace) => {
+                    viewer.close();
+                    AppReturn::Continue
+                }
+                _ => AppReturn::Continue,
+            };
+        }
+
         if let Some(action) = self.actions.find(key) {
             match action {
                 Action::Quit => AppReturn::Exit,
                 Action::Edit => {
+                    if self.state.game_status != GameStatus::InProgress {
+                        return AppReturn::Continue;
+                    }
+
                     if let Key::Char(character) = key {
                         self.state.input.push(character);
+                        self.state.message = None;
                     }
 
                     AppReturn::Continue
                 }
                 Action::Submit => {
-                    let word = self.state.input.drain(..).collect::<String>();
+                    // Once the game is over, Enter goes from the result to the stats and the
+                    // analysis
+                    if self.state.game_status != GameStatus::InProgress {
+                        self.end_screen = self.end_screen.next();
+                        if !self.shows_stats() && self.end_screen == EndScreen::Stats {
+                            self.end_screen = self.end_screen.next();
+                        }
+
+                        return AppReturn::Continue;
+                    }
+
+                    let word = self.state.input.clone();
 
-                    // Because we're only allowing letters (and not any
-                    // graphemes), we can get away with just using the len method
-                    // to count bytes rather than characters
-                    if word.len() == 5 && words::check_validity(&word) {
+                    if let Some(problem) = self.word_problem(&word) {
+                        self.state.message = Some(problem);
+                    } else if let Some(violation) = self.state.hard_mode_violation(&word) {
+                        // Rejected guesses stay in the input so they can be fixed
+                        self.state.message = Some(violation);
+                    } else {
+                        self.state.input.clear();
+                        self.state.message = None;
                         self.state.guesses.push(word);
 
                         AppState::check_word_status(&mut self.state);
                         AppState::increment_attempt(&mut self.state);
+                        self.refresh_hints();
+
+                        if self.versus.is_some() {
+                            self.dispatch(IoEvent::SendProgress).await;
+                        }
                     }
 
                     AppReturn::Continue
                 }
                 Action::Backspace => {
                     self.state.input.pop();
+                    self.state.message = None;
 
                     AppReturn::Continue
                 }
+                Action::Resume => {
+                    if let Some(saved) = self.resume.take() {
+                        self.state = saved.restore(&self.options);
+                    }
+                    self.play();
+
+                    AppReturn::Continue
+                }
+                Action::NewGame => {
+                    self.resume = None;
+                    match &mut self.hot_seat {
+                        Some(hot_seat) => {
+                            hot_seat.next_round();
+                            self.choose_word();
+                        }
+                        None => {
+                            // The clock was running while the player was asked about the game
+                            // they left unfinished
+                            self.state.stopwatch = Stopwatch::starting_from(Duration::ZERO);
+                            self.play();
+                        }
+                    }
+
+                    AppReturn::Continue
+                }
+                Action::SetWord => {
+                    let word = self.state.input.clone();
+
+                    match self.word_problem(&word) {
+                        Some(problem) => self.state.message = Some(problem),
+                        None => self.start_round(word),
+                    }
+
+                    AppReturn::Continue
+                }
+                // Only used in the history screen
+                Action::SelectPrevious
+                | Action::SelectNext
+                | Action::StepForward
+                | Action::StepBack => AppReturn::Continue,
             }
         } else {
             AppReturn::Continue
@@ -80,24 +261,123 @@ impl App {
 
     /// We could update the app or dispatch event on tick
     pub async fn update_on_tick(&mut self) -> AppReturn {
+        if self.state.game_status != GameStatus::InProgress {
+            return AppReturn::Continue;
+        }
+
+        let out_of_time = self.out_of_time();
+        if self.state.guesses.is_empty() && !out_of_time {
+            return AppReturn::Continue;
+        }
+
+        // A word picked by the other player doesn't count towards anyone's stats or history,
+        // just the score between the two of them
+        if let Some(hot_seat) = &mut self.hot_seat {
+            let solved = self.state.all_solved();
+            if solved || self.state.out_of_attempts() {
+                if solved {
+                    AppState::game_won(&mut self.state);
+                } else {
+                    AppState::game_lost(&mut self.state);
+                }
+                hot_seat.round_over(solved);
+                self.actions = vec![Action::Quit, Action::Submit, Action::NewGame].into();
+                self.dispatch(IoEvent::Analyse).await;
+            }
+
+            return AppReturn::Continue;
+        }
+
+        // A speedrun goes straight on to the next word, and only the run as a whole counts
+        // towards the stats
+        if let Some(speedrun) = &mut self.speedrun {
+            if self.state.all_solved() {
+                speedrun.solved += 1;
+                AppState::game_won(&mut self.state);
+                self.record_history().await;
+                self.next_word();
+            } else if out_of_time || self.state.out_of_attempts() {
+                AppState::game_lost(&mut self.state);
+                speedrun.clock.stop();
+                self.stats.record_speedrun(speedrun.limit, speedrun.solved);
+                self.dispatch(IoEvent::SaveStats).await;
+                self.dispatch(IoEvent::Analyse).await;
+                self.record_history().await;
+            }
+
+            return AppReturn::Continue;
+        }
+
+        // Survival carries on with the next word for as long as there are guesses left for it
+        if let Some(survival) = &mut self.survival {
+            let solved = self.state.all_solved();
+            if solved || self.state.out_of_attempts() {
+                survival.word_over(self.state.guesses.len(), solved);
+                let run_over = survival.is_over();
+                if solved {
+                    AppState::game_won(&mut self.state);
+                } else {
+                    AppState::game_lost(&mut self.state);
+                }
+                self.record_history().await;
+
+                if run_over {
+                    self.dispatch(IoEvent::Analyse).await;
+                } else {
+                    self.next_word();
+                }
+            }
+
+            return AppReturn::Continue;
+        }
+
         // Check if we won or lost
-        if let Some(last_guess) = self.state.guesses.last() {
-            if last_guess == &self.state.solution {
-                AppState::game_won(&mut self.state)
-            } else if self.state.attempt >= 6 {
-                AppState::game_lost(&mut self.state)
+        let counts = self.counts_towards_stats();
+        if self.state.all_solved() {
+            AppState::game_won(&mut self.state);
+            if counts {
+                self.stats.record_win(self.state.guesses.len());
+                self.dispatch(IoEvent::SaveStats).await;
             }
+            self.dispatch(IoEvent::Analyse).await;
+            self.record_history().await;
+        } else if out_of_time || self.state.out_of_attempts() {
+            AppState::game_lost(&mut self.state);
+            if counts {
+                self.stats.record_loss();
+                self.dispatch(IoEvent::SaveStats).await;
+            }
+            self.dispatch(IoEvent::Analyse).await;
+            self.record_history().await;
+        }
+
+        // The other player hears about it as soon as the game is over
+        if self.versus.is_some() && self.state.game_status != GameStatus::InProgress {
+            self.dispatch(IoEvent::SendProgress).await;
         }
 
         AppReturn::Continue
     }
 
+    /// Sends the game that's just finished to the history file. The entry is made straight away,
+    /// since speedruns and survival runs have gone on to the next word by the time it's written.
+    async fn record_history(&mut self) {
+        let versus = self.versus.is_some();
+        let Some(entry) =
+            HistoryEntry::from_state(&self.state, &self.options, versus, SystemClock.now())
+        else {
+            return;
+        };
+
+        self.dispatch(IoEvent::RecordHistory(Box::new(entry))).await;
+    }
+
     /// Send a network event to the IO thread
     pub async fn dispatch(&mut self, action: IoEvent) {
-        // `is_loading` will be set to false again after the async action has finished in io/handler.rs
-        self.is_loading = true;
+        // Counted down again once the async action has finished in io/handler.rs
+        self.loading += 1;
         if let Err(_e) = self.io_tx.send(action).await {
-            self.is_loading = false;
+            self.loaded();
         };
     }
 
@@ -108,11 +388,384 @@ impl App {
         &self.state
     }
 
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    pub fn hints(&self) -> Option<&Hints> {
+        self.hints.as_ref()
+    }
+
+    /// Whether the solver is still working out the hints for the latest guess
+    pub fn hints_pending(&self) -> bool {
+        self.hints_pending
+    }
+
+    pub fn solver(&self) -> Arc<Solver> {
+        self.solver.clone()
+    }
+
+    pub fn end_screen(&self) -> EndScreen {
+        self.end_screen
+    }
+
+    pub fn analysis(&self) -> Option<&[GuessAnalysis]> {
+        self.analysis.as_deref()
+    }
+
+    pub fn options(&self) -> &GameOptions {
+        &self.options
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.options.theme
+    }
+
+    pub fn analysed(&mut self, analysis: Vec<GuessAnalysis>) {
+        self.analysis = Some(analysis);
+    }
+
+    /// Whether any IO events are still waiting to be handled, like stats to be saved
     pub fn is_loading(&self) -> bool {
-        self.is_loading
+        self.loading > 0
+    }
+
+    pub fn loaded(&mut self) {
+        self.loading = self.loading.saturating_sub(1);
+    }
+
+    pub fn history(&self) -> Option<&HistoryViewer> {
+        self.history.as_ref()
+    }
+
+    /// Opens the past games screen instead of starting a game, once the history is loaded
+    pub fn view_history(&mut self) {
+        self.history = Some(HistoryViewer::default());
+    }
+
+    pub fn history_loaded(&mut self, entries: Vec<HistoryEntry>) {
+        self.history = Some(HistoryViewer::new(entries));
+        self.actions = vec![
+            Action::Quit,
+            Action::SelectPrevious,
+            Action::SelectNext,
+            Action::StepForward,
+            Action::StepBack,
+            Action::Backspace,
+        ]
+        .into();
+    }
+
+    pub fn versus(&self) -> Option<&Versus> {
+        self.versus.as_ref()
+    }
+
+    /// Plays against someone else over the network instead of alone. Nothing can be typed
+    /// until they're there.
+    pub fn start_versus(&mut self, role: Role, address: String) {
+        self.versus = Some(Versus::new(role, address));
+    }
+
+    /// The host's game starts as soon as the guest joins
+    pub fn opponent_joined(&mut self) {
+        if let Some(versus) = &mut self.versus {
+            versus.connection = Connection::Playing;
+            self.state.stopwatch = Stopwatch::starting_from(Duration::ZERO);
+            self.play();
+        }
+    }
+
+    /// The guest's game starts with the host's word, as long as it's one the guest's game could
+    /// have picked. Otherwise none of the guest's guesses could ever match it, so the guest is
+    /// told what's wrong instead.
+    pub fn versus_started(&mut self, solution: String, max_attempts: Option<usize>) {
+        let mismatch = if solution.len() != self.options.word_length {
+            Some(format!(
+                "The host is playing {} letter words and this game has {}. Both players need the same --length.",
+                solution.len(),
+                self.options.word_length
+            ))
+        } else if !self.options.words.check_validity(&solution) {
+            Some(String::from(
+                "The host's word isn't in this game's word list. Both players need the same --answers and --allowed.",
+            ))
+        } else {
+            None
+        };
+
+        if let Some(versus) = &mut self.versus {
+            if mismatch.is_some() {
+                versus.connection = Connection::Mismatched;
+                versus.mismatch = mismatch;
+                return;
+            }
+
+            versus.connection = Connection::Playing;
+            self.state = AppState {
+                word_length: solution.len(),
+                boards: vec![Board::new(solution)],
+                max_attempts,
+                ..AppState::new(&self.options)
+            };
+            self.play();
+        }
+    }
+
+    pub fn opponent_progress(&mut self, rows: Vec<Vec<LetterStatus>>, status: PlayerStatus) {
+        if let Some(versus) = &mut self.versus {
+            versus.opponent_rows = rows;
+            versus.opponent_status = status;
+        }
+    }
+
+    pub fn opponent_left(&mut self) {
+        if let Some(versus) = &mut self.versus {
+            if versus.connection != Connection::Mismatched {
+                versus.connection = Connection::Left;
+            }
+        }
+    }
+
+    pub fn hot_seat(&self) -> Option<&HotSeat> {
+        self.hot_seat.as_ref()
+    }
+
+    /// Hands the keyboard to the player picking the next word, which is typed into the input
+    /// like a guess but kept hidden
+    fn choose_word(&mut self) {
+        self.state = AppState::new(&self.options);
+        self.end_screen = EndScreen::Result;
+        self.hints = None;
+        self.hints_pending = false;
+        self.analysis = None;
+        self.actions = vec![
+            Action::Quit,
+            Action::Backspace,
+            Action::SetWord,
+            Action::Edit,
+        ]
+        .into();
     }
 
-    pub fn initialized(&mut self) {
+    /// Hands the keyboard over to the other player to guess the word that was picked
+    fn start_round(&mut self, solution: String) {
+        if let Some(hot_seat) = &mut self.hot_seat {
+            hot_seat.choosing = false;
+            self.state = AppState {
+                boards: vec![Board::new(solution)],
+                ..AppState::new(&self.options)
+            };
+            self.play();
+        }
+    }
+
+    /// The game left unfinished last time, while the player is asked about it
+    pub fn resume_offer(&self) -> Option<&SavedGame> {
+        self.resume.as_ref()
+    }
+
+    /// Whether a finished game goes in the stats. Only games played the usual way do: one board
+    /// with 6 guesses, not Absurdle, and no clock, survival run or other player. Other games
+    /// would mix streaks and guess counts that can't be compared with each other. It goes by the
+    /// game being played rather than the options, which a resumed game doesn't have to match.
+    pub fn counts_towards_stats(&self) -> bool {
+        self.state.boards.len() == 1
+            && self.state.max_attempts == Some(DISTRIBUTION_LEN)
+            && self.state.candidates.is_none()
+            && self.options.time_limit.is_none()
+            && self.survival.is_none()
+            && self.hot_seat.is_none()
+            && self.versus.is_none()
+    }
+
+    /// Whether the stats screen comes after the result. Only games that count towards the stats
+    /// show them, and speedruns for their bests.
+    pub fn shows_stats(&self) -> bool {
+        self.counts_towards_stats() || self.speedrun.is_some()
+    }
+
+    /// What to save when the player quits: the game they're in the middle of, or the one they
+    /// haven't decided about yet
+    pub fn saved_game(&self) -> Option<SavedGame> {
+        if !self.keeps_saved_games() {
+            return None;
+        }
+
+        self.resume
+            .clone()
+            .or_else(|| SavedGame::from_state(&self.state))
+    }
+
+    /// Whether games can be saved and picked up again. A head-to-head game can't be without the
+    /// other player, a hot seat game without the score, or a word from a speedrun or survival
+    /// run without the rest of the run.
+    pub fn keeps_saved_games(&self) -> bool {
+        let run = matches!(self.options.time_limit, Some(TimeLimit::Speedrun(_)))
+            || self.options.survival.is_some();
+
+        self.versus.is_none() && self.hot_seat.is_none() && !run
+    }
+
+    pub fn speedrun(&self) -> Option<&Speedrun> {
+        self.speedrun.as_ref()
+    }
+
+    pub fn survival(&self) -> Option<&Survival> {
+        self.survival.as_ref()
+    }
+
+    /// Goes straight on to a new word in a speedrun or survival run, instead of stopping at the
+    /// end of the game
+    fn next_word(&mut self) {
+        self.state = AppState::new(&self.options);
+        if let Some(survival) = &self.survival {
+            self.state.max_attempts = Some(survival.attempts());
+        }
+
+        self.play();
+    }
+
+    /// How the game went, once it's over
+    pub fn result(&self) -> Option<String> {
+        // A survival run only stops once it's out of guesses, whether the last word was solved
+        // or not
+        if let Some(survival) = &self.survival {
+            let missed = match self.state.game_status {
+                GameStatus::InProgress => return None,
+                GameStatus::Lose => {
+                    format!(" The last word was \"{}\".", self.state.boards[0].solution)
+                }
+                _ => String::new(),
+            };
+
+            return Some(format!(
+                "Out of guesses!{}\nYou solved {} using {} guesses.",
+                missed,
+                words(survival.solved),
+                survival.used
+            ));
+        }
+
+        match self.state.game_status {
+            GameStatus::Win => Some(format!(
+                "You have won! It took {} attempts.",
+                self.state.attempt
+            )),
+            GameStatus::Lose => {
+                let unsolved: Vec<String> = self
+                    .state
+                    .boards
+                    .iter()
+                    .filter(|board| !board.is_solved())
+                    .map(|board| format!("\"{}\"", board.solution))
+                    .collect();
+                let answer = match (&self.state.candidates, unsolved.as_slice()) {
+                    // Absurdle never settled on a word, so there's no one answer to give away
+                    (Some(candidates), _) if candidates.len() > 1 => {
+                        format!("It could still have been {} words", candidates.len())
+                    }
+                    (_, [solution]) => format!("The correct word was {}", solution),
+                    (_, solutions) => {
+                        format!("The missing words were {}", solutions.join(", "))
+                    }
+                };
+
+                let lost = match self.time_left() {
+                    Some(left) if left.is_zero() => "Time's up!",
+                    _ => "You lost.",
+                };
+                let run = match &self.speedrun {
+                    Some(speedrun) => {
+                        let words = words(speedrun.solved);
+                        match speedrun.previous_best {
+                            _ if speedrun.is_new_best() => {
+                                format!("\nYou solved {}, a new best!", words)
+                            }
+                            Some(best) => {
+                                format!("\nYou solved {}. Your best is {}.", words, best)
+                            }
+                            None => format!("\nYou solved {}.", words),
+                        }
+                    }
+                    None => String::new(),
+                };
+
+                Some(format!("{} {}.{}", lost, answer, run))
+            }
+            _ => None,
+        }
+    }
+
+    /// How long is left on the clock, in a timed game
+    pub fn time_left(&self) -> Option<Duration> {
+        match self.options.time_limit? {
+            TimeLimit::Countdown(limit) => {
+                Some(limit.saturating_sub(self.state.stopwatch.elapsed()))
+            }
+            TimeLimit::Speedrun(_) => self.speedrun.as_ref().map(Speedrun::remaining),
+        }
+    }
+
+    /// Whether the clock has run out. It doesn't while the player is being asked about an
+    /// unfinished game or waiting for an opponent, and the clock starts over once they're done.
+    fn out_of_time(&self) -> bool {
+        let waiting = self.resume.is_some()
+            || self.history.is_some()
+            || self.versus.as_ref().is_some_and(|versus| {
+                matches!(
+                    versus.connection,
+                    Connection::Waiting | Connection::Mismatched
+                )
+            });
+
+        !waiting && self.time_left() == Some(Duration::ZERO)
+    }
+
+    /// Starts the first game, or picks up the one left unfinished last time. Today's daily
+    /// puzzle carries on by itself, and any other unfinished game is offered first.
+    pub fn initialized(&mut self, stats: Stats, saved: Option<SavedGame>, todays_puzzle: u32) {
+        self.stats = stats;
+
+        // A head-to-head game starts once both players are there, which might already be the
+        // case by now
+        if self.versus.is_some() {
+            return;
+        }
+        self.state = AppState::new(&self.options);
+
+        if self.hot_seat.is_some() {
+            self.choose_word();
+            return;
+        }
+
+        if let Some(TimeLimit::Speedrun(limit)) = self.options.time_limit {
+            self.speedrun = Some(Speedrun::new(limit, self.stats.speedrun_best(limit)));
+        }
+        if let Some(pool) = self.options.survival {
+            let per_word = self
+                .state
+                .max_attempts
+                .unwrap_or(options::DEFAULT_MAX_ATTEMPTS);
+            self.survival = Some(Survival::new(pool, per_word));
+            self.next_word();
+            return;
+        }
+
+        match saved {
+            Some(saved) if saved.resumes_automatically(&self.options, todays_puzzle) => {
+                self.state = saved.restore(&self.options);
+                self.play();
+            }
+            Some(saved) => {
+                self.resume = Some(saved);
+                self.actions = vec![Action::Quit, Action::Resume, Action::NewGame].into();
+            }
+            None => self.play(),
+        }
+    }
+
+    /// Hands the keyboard over to the game
+    fn play(&mut self) {
         // Update contextual actions
         self.actions = vec![
             Action::Quit,
@@ -122,6 +775,83 @@ impl App {
         ]
         .into();
 
-        self.state = AppState::new()
+        self.refresh_hints();
+    }
+
+    /// Why a guess, or the word picked for a hot seat round, can't be played
+    fn word_problem(&self, word: &str) -> Option<String> {
+        // Because we're only allowing letters (and not any
+        // graphemes), we can get away with just using the len method
+        // to count bytes rather than characters
+        if word.len() < self.state.word_length {
+            Some(String::from("Not enough letters"))
+        } else if word.len() > self.state.word_length {
+            Some(String::from("Too many letters"))
+        } else if !self.options.words.check_validity(word) {
+            Some(String::from("Not in word list"))
+        } else {
+            None
+        }
+    }
+
+    /// Asks the solver about the board as it is now. Ranking every guess takes too long to do
+    /// while the UI is waiting on the app, so it's done on the IO thread and the hint panel
+    /// waits for it.
+    fn refresh_hints(&mut self) {
+        if !self.options.hints {
+            return;
+        }
+
+        // Called from places that can't wait on the channel, which has plenty of room anyway
+        self.loading += 1;
+        match self.io_tx.try_send(IoEvent::Hints) {
+            Ok(()) => self.hints_pending = true,
+            Err(_e) => self.loaded(),
+        }
+    }
+
+    /// The guesses so far and the colours of the first board that isn't solved yet, which the
+    /// hints are about
+    pub fn hints_question(&self) -> (Vec<String>, Vec<Vec<LetterStatus>>) {
+        let state = &self.state;
+        let board = match state.boards.iter().find(|board| !board.is_solved()) {
+            Some(board) => board,
+            None => &state.boards[0],
+        };
+
+        (state.guesses.clone(), board.square_statuses.clone())
+    }
+
+    /// The solver's answer to `hints_question`. An answer to a question that's out of date, from
+    /// before another guess was made, is dropped. Suggestions that hard mode wouldn't accept are
+    /// left out.
+    pub fn hints_ranked(
+        &mut self,
+        question: (Vec<String>, Vec<Vec<LetterStatus>>),
+        candidates: usize,
+        ranking: Vec<Suggestion>,
+    ) {
+        if question != self.hints_question() {
+            return;
+        }
+
+        let state = &self.state;
+        self.hints = Some(Hints {
+            candidates,
+            suggestions: ranking
+                .into_iter()
+                .filter(|suggestion| state.hard_mode_violation(&suggestion.word).is_none())
+                .take(HINT_COUNT)
+                .collect(),
+        });
+        self.hints_pending = false;
+    }
+}
+
+/// "1 word" or "2 words"
+fn words(count: u32) -> String {
+    match count {
+        1 => String::from("1 word"),
+        count => format!("{} words", count),
     }
 }
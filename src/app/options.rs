@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::theme::Theme;
+use super::words::{WordList, DEFAULT_WORD_LENGTH};
+
+/// How many guesses the original game gives you
+pub const DEFAULT_MAX_ATTEMPTS: usize = 6;
+
+/// How many guesses a game gets for the number of boards in it, from the original game,
+/// Dordle, Quordle and Octordle
+pub fn default_max_attempts(boards: usize) -> usize {
+    match boards {
+        1 => DEFAULT_MAX_ATTEMPTS,
+        2 => 7,
+        4 => 9,
+        8 => 13,
+        _ => DEFAULT_MAX_ATTEMPTS + boards,
+    }
+}
+
+/// A clock the game is played against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeLimit {
+    /// The word has to be solved before the time is up
+    Countdown(Duration),
+    /// Solve as many words as possible, one after another, before the time is up
+    Speedrun(Duration),
+}
+
+/// The rules a game is played with, chosen once at startup
+#[derive(Debug, Clone)]
+pub struct GameOptions {
+    /// Any revealed hints must be used in every later guess
+    pub hard_mode: bool,
+    /// Play the daily puzzle with this number instead of a random word
+    pub puzzle: Option<u32>,
+    /// Play this word instead of a random one, from a puzzle someone made
+    pub word: Option<String>,
+    /// Share the result with orange and blue squares instead of green and yellow
+    pub color_blind: bool,
+    /// The colours the board and keyboard are drawn in
+    pub theme: Theme,
+    /// How many letters the solution and every guess have
+    pub word_length: usize,
+    /// How many words are played at once, each on its own board
+    pub boards: usize,
+    /// How many guesses the player gets, or `None` for unlimited practice
+    pub max_attempts: Option<usize>,
+    /// Play Absurdle, where the game dodges every guess instead of picking a word up front
+    pub absurdle: bool,
+    /// Show the solver's suggestions next to the board
+    pub hints: bool,
+    /// Two players at one keyboard take turns picking the word and guessing it
+    pub hot_seat: bool,
+    /// Play against the clock
+    pub time_limit: Option<TimeLimit>,
+    /// Play words one after another from a single pool of this many guesses
+    pub survival: Option<usize>,
+    /// Where solutions come from and which guesses are accepted, already narrowed down to
+    /// words of `word_length` letters
+    pub words: Arc<WordList>,
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        GameOptions {
+            hard_mode: false,
+            puzzle: None,
+            word: None,
+            color_blind: false,
+            theme: Theme::default(),
+            word_length: DEFAULT_WORD_LENGTH,
+            boards: 1,
+            max_attempts: Some(DEFAULT_MAX_ATTEMPTS),
+            absurdle: false,
+            hints: false,
+            hot_seat: false,
+            time_limit: None,
+            survival: None,
+            words: WordList::builtin(),
+        }
+    }
+}
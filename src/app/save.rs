@@ -0,0 +1,413 @@
+use std::path::Path;
+use std::time::Duration;
+
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::options::{default_max_attempts, GameOptions};
+use super::state::{AppState, Board, GameStatus, LetterStatus, Stopwatch};
+use super::words::DEFAULT_WORD_LENGTH;
+use crate::io::storage;
+
+/// The version of the save format this build writes. Saves from a newer build are ignored
+/// rather than misread, and older ones can be upgraded as the format changes.
+pub const SAVE_VERSION: u32 = 1;
+
+/// A game that was quit before it was over, so it can be picked up again next time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub version: u32,
+    pub boards: Vec<SavedBoard>,
+    pub guesses: Vec<String>,
+    pub attempt: usize,
+    pub max_attempts: Option<usize>,
+    pub word_length: usize,
+    pub hard_mode: bool,
+    pub puzzle_number: Option<u32>,
+    /// The words Absurdle was still dodging between
+    #[serde(default)]
+    pub candidates: Option<Vec<String>>,
+    /// How long had been spent on the game, in milliseconds
+    #[serde(default)]
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedBoard {
+    pub solution: String,
+    /// The colours of each row the board took, with `G` for green, `Y` for yellow and `-` for
+    /// letters that aren't in the word
+    pub rows: Vec<String>,
+}
+
+impl SavedGame {
+    /// Keeps a game that's been started and isn't over yet. There's nothing worth saving
+    /// otherwise.
+    pub fn from_state(state: &AppState) -> Option<SavedGame> {
+        if state.game_status != GameStatus::InProgress || state.guesses.is_empty() {
+            return None;
+        }
+
+        Some(SavedGame {
+            version: SAVE_VERSION,
+            boards: state.boards.iter().map(SavedBoard::from_board).collect(),
+            guesses: state.guesses.clone(),
+            attempt: state.attempt,
+            max_attempts: state.max_attempts,
+            word_length: state.word_length,
+            hard_mode: state.hard_mode,
+            puzzle_number: state.puzzle_number,
+            candidates: state.candidates.clone(),
+            elapsed_ms: state.stopwatch.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// The file the save for this kind of game goes in. Each kind has its own, so quitting one
+    /// kind of game partway doesn't lose the save of another, and the usual game keeps
+    /// `save.json`. Daily puzzles share one, as do puzzle codes.
+    pub fn file_name(options: &GameOptions) -> String {
+        let mut kind = Vec::new();
+        if options.word_length != DEFAULT_WORD_LENGTH {
+            kind.push(format!("{}-letters", options.word_length));
+        }
+        if options.boards > 1 {
+            kind.push(format!("{}-boards", options.boards));
+        }
+        // Absurdle is unlimited unless it's given a number of guesses
+        let usual_attempts = (!options.absurdle).then(|| default_max_attempts(options.boards));
+        if options.max_attempts != usual_attempts {
+            kind.push(match options.max_attempts {
+                Some(attempts) => format!("{}-guesses", attempts),
+                None => String::from("unlimited"),
+            });
+        }
+        if options.hard_mode {
+            kind.push(String::from("hard"));
+        }
+        if options.absurdle {
+            kind.push(String::from("absurdle"));
+        }
+        if options.puzzle.is_some() {
+            kind.push(String::from("daily"));
+        }
+        if options.word.is_some() {
+            kind.push(String::from("code"));
+        }
+
+        match kind.is_empty() {
+            true => String::from("save.json"),
+            false => format!("save-{}.json", kind.join("-")),
+        }
+    }
+
+    /// Reads the saved game, if there is one this build understands
+    pub fn load(path: &Path) -> Result<Option<SavedGame>> {
+        let saved: Option<SavedGame> = storage::load_or_backup(path)?;
+
+        Ok(saved.filter(|saved| saved.version <= SAVE_VERSION))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        storage::save(path, self)
+    }
+
+    /// Whether this is the kind of game asked for this time: the same length, number of boards,
+    /// number of guesses, hard mode or not and Absurdle or not, and the same daily puzzle or
+    /// puzzle code if there is one. Anything else would replace the game that was asked for with
+    /// a different one. An Absurdle game with no words left to dodge with can't be played on, so
+    /// it never fits.
+    pub fn fits(&self, options: &GameOptions) -> bool {
+        let same_word = match &options.word {
+            Some(word) => self.boards.iter().all(|board| board.solution == *word),
+            None => true,
+        };
+
+        self.word_length == options.word_length
+            && self.boards.len() == options.boards
+            && self.max_attempts == options.max_attempts
+            && self.hard_mode == options.hard_mode
+            && self.candidates.is_some() == options.absurdle
+            && self
+                .candidates
+                .as_ref()
+                .is_none_or(|words| !words.is_empty())
+            && self.puzzle_number == options.puzzle
+            && same_word
+    }
+
+    /// Today's daily puzzle is picked up without asking, if that's what was asked for
+    pub fn resumes_automatically(&self, options: &GameOptions, todays_puzzle: u32) -> bool {
+        self.fits(options) && options.puzzle == Some(todays_puzzle)
+    }
+
+    /// Puts the game back the way it was, with the keyboard worked out again from the rows
+    pub fn restore(&self, options: &GameOptions) -> AppState {
+        AppState {
+            boards: self
+                .boards
+                .iter()
+                .map(|board| board.restore(&self.guesses))
+                .collect(),
+            guesses: self.guesses.clone(),
+            attempt: self.attempt,
+            max_attempts: self.max_attempts,
+            word_length: self.word_length,
+            hard_mode: self.hard_mode,
+            color_blind: options.color_blind,
+            puzzle_number: self.puzzle_number,
+            candidates: self.candidates.clone(),
+            stopwatch: Stopwatch::starting_from(Duration::from_millis(self.elapsed_ms)),
+            ..AppState::default()
+        }
+    }
+}
+
+impl SavedBoard {
+    pub fn from_board(board: &Board) -> SavedBoard {
+        SavedBoard {
+            solution: board.solution.clone(),
+            rows: board
+                .square_statuses
+                .iter()
+                .map(|colors| encode_row(colors))
+                .collect(),
+        }
+    }
+
+    /// Rebuilds the board as it was after the given guesses, which can stop short of all of
+    /// them
+    pub fn restore(&self, guesses: &[String]) -> Board {
+        let mut board = Board::new(self.solution.clone());
+        for (guess, row) in guesses.iter().zip(&self.rows) {
+            board.record(guess, decode_row(row));
+        }
+
+        board
+    }
+}
+
+/// A row of colours as text, with `G` for green, `Y` for yellow and `-` for letters that
+/// aren't in the word
+pub fn encode_row(colors: &[LetterStatus]) -> String {
+    colors.iter().map(|color| encode(*color)).collect()
+}
+
+pub fn decode_row(row: &str) -> Vec<LetterStatus> {
+    row.chars().map(decode).collect()
+}
+
+fn encode(status: LetterStatus) -> char {
+    match status {
+        LetterStatus::Correct => 'G',
+        LetterStatus::Present => 'Y',
+        _ => '-',
+    }
+}
+
+fn decode(letter: char) -> LetterStatus {
+    match letter {
+        'G' => LetterStatus::Correct,
+        'Y' => LetterStatus::Present,
+        _ => LetterStatus::Absent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played(solution: &str, guesses: &[&str]) -> AppState {
+        let mut state = AppState::with_solution(solution);
+        for guess in guesses {
+            state.guesses.push(guess.to_string());
+            state.check_word_status();
+            state.increment_attempt();
+        }
+
+        state
+    }
+
+    #[test]
+    fn games_come_back_the_way_they_were() {
+        let state = played("those", &["crane", "geese"]);
+
+        let saved = SavedGame::from_state(&state).unwrap();
+        assert_eq!(saved.boards[0].rows, vec!["----G", "---GG"]);
+        let restored = saved.restore(&GameOptions::default());
+
+        assert_eq!(restored.guesses, state.guesses);
+        assert_eq!(restored.attempt, 2);
+        assert_eq!(
+            restored.boards[0].square_statuses,
+            state.boards[0].square_statuses
+        );
+        assert_eq!(
+            restored.boards[0].key_statuses,
+            state.boards[0].key_statuses
+        );
+        assert!(restored.game_status == GameStatus::InProgress);
+    }
+
+    #[test]
+    fn only_unfinished_games_are_saved() {
+        assert_eq!(SavedGame::from_state(&played("those", &[])), None);
+
+        let mut won = played("those", &["those"]);
+        won.game_won();
+        assert_eq!(SavedGame::from_state(&won), None);
+    }
+
+    #[test]
+    fn saves_from_newer_versions_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("save.json");
+        let mut saved = SavedGame::from_state(&played("those", &["crane"])).unwrap();
+
+        saved.save(&path).unwrap();
+        assert_eq!(SavedGame::load(&path).unwrap(), Some(saved.clone()));
+
+        saved.version = SAVE_VERSION + 1;
+        saved.save(&path).unwrap();
+        assert_eq!(SavedGame::load(&path).unwrap(), None);
+
+        assert_eq!(
+            SavedGame::load(&dir.path().join("missing.json")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn only_todays_puzzle_resumes_by_itself() {
+        let daily = |puzzle| GameOptions {
+            puzzle: Some(puzzle),
+            ..GameOptions::default()
+        };
+        let mut saved = SavedGame::from_state(&played("those", &["crane"])).unwrap();
+        assert!(!saved.resumes_automatically(&GameOptions::default(), 412));
+
+        saved.puzzle_number = Some(411);
+        assert!(!saved.resumes_automatically(&daily(412), 412));
+        assert!(!saved.resumes_automatically(&daily(411), 412));
+        assert!(saved.resumes_automatically(&daily(411), 411));
+        assert!(!saved.resumes_automatically(&GameOptions::default(), 411));
+    }
+
+    #[test]
+    fn every_kind_of_game_has_its_own_save() {
+        let file = |options| SavedGame::file_name(&options);
+
+        assert_eq!(file(GameOptions::default()), "save.json");
+        assert_eq!(
+            file(GameOptions {
+                word_length: 6,
+                boards: 4,
+                max_attempts: Some(9),
+                ..GameOptions::default()
+            }),
+            "save-6-letters-4-boards.json"
+        );
+        assert_eq!(
+            file(GameOptions {
+                boards: 2,
+                max_attempts: Some(7),
+                ..GameOptions::default()
+            }),
+            "save-2-boards.json"
+        );
+        assert_eq!(
+            file(GameOptions {
+                absurdle: true,
+                max_attempts: None,
+                ..GameOptions::default()
+            }),
+            "save-absurdle.json"
+        );
+        assert_eq!(
+            file(GameOptions {
+                max_attempts: None,
+                ..GameOptions::default()
+            }),
+            "save-unlimited.json"
+        );
+        assert_eq!(
+            file(GameOptions {
+                max_attempts: Some(8),
+                hard_mode: true,
+                ..GameOptions::default()
+            }),
+            "save-8-guesses-hard.json"
+        );
+        assert_eq!(
+            file(GameOptions {
+                puzzle: Some(411),
+                ..GameOptions::default()
+            }),
+            file(GameOptions {
+                puzzle: Some(412),
+                ..GameOptions::default()
+            })
+        );
+    }
+
+    #[test]
+    fn saves_only_fit_the_same_kind_of_game() {
+        let saved = SavedGame::from_state(&played("those", &["crane"])).unwrap();
+        assert!(saved.fits(&GameOptions::default()));
+
+        for options in [
+            GameOptions {
+                word_length: 6,
+                ..GameOptions::default()
+            },
+            GameOptions {
+                boards: 2,
+                ..GameOptions::default()
+            },
+            GameOptions {
+                absurdle: true,
+                ..GameOptions::default()
+            },
+            GameOptions {
+                puzzle: Some(412),
+                ..GameOptions::default()
+            },
+            GameOptions {
+                word: Some(String::from("crane")),
+                ..GameOptions::default()
+            },
+            GameOptions {
+                max_attempts: None,
+                ..GameOptions::default()
+            },
+            GameOptions {
+                hard_mode: true,
+                ..GameOptions::default()
+            },
+        ] {
+            assert!(!saved.fits(&options), "{:?}", options);
+        }
+        assert!(saved.fits(&GameOptions {
+            word: Some(String::from("those")),
+            ..GameOptions::default()
+        }));
+    }
+
+    #[test]
+    fn absurdle_saves_without_candidates_never_fit() {
+        let options = GameOptions {
+            absurdle: true,
+            max_attempts: None,
+            ..GameOptions::default()
+        };
+        let mut state = AppState::new(&options);
+        state.guesses.push(String::from("crane"));
+        state.check_word_status();
+        state.increment_attempt();
+
+        let mut saved = SavedGame::from_state(&state).unwrap();
+        assert!(saved.fits(&options));
+
+        saved.candidates = Some(Vec::new());
+        assert!(!saved.fits(&options));
+    }
+}
@@ -0,0 +1,208 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use eyre::Result;
+
+use super::state::{AppState, GameStatus, LetterStatus};
+
+/// What to do with the result once the game is over
+#[derive(Debug, Clone, Default)]
+pub struct ShareOptions {
+    /// Also write the result to this file
+    pub file: Option<PathBuf>,
+    /// Copy the result to the clipboard with an OSC 52 escape sequence
+    pub clipboard: bool,
+}
+
+/// Builds the spoiler free result for a finished game, like "Rustle #412 4/6*" followed by a row
+/// of squares per guess. Returns `None` while the game is still going.
+pub fn share_text(state: &AppState) -> Option<String> {
+    let score = match state.game_status {
+        GameStatus::Win => state.guesses.len().to_string(),
+        GameStatus::Lose => String::from("X"),
+        GameStatus::InProgress => return None,
+    };
+
+    let mut text = String::from("Rustle");
+    if state.boards.len() > 1 {
+        text.push_str(&format!(" x{}", state.boards.len()));
+    }
+    if state.candidates.is_some() {
+        text.push_str(" Absurdle");
+    }
+    if let Some(puzzle) = state.puzzle_number {
+        text.push_str(&format!(" #{}", puzzle));
+    }
+    match state.max_attempts {
+        Some(max_attempts) => text.push_str(&format!(" {}/{}", score, max_attempts)),
+        None => text.push_str(&format!(" {}/∞", score)),
+    }
+    // Hard mode games get a star, like the original
+    if state.hard_mode {
+        text.push('*');
+    }
+    text.push('\n');
+
+    for (i, board) in state.boards.iter().enumerate() {
+        // Boards are separated by a blank line
+        if i > 0 {
+            text.push('\n');
+        }
+
+        for colors in &board.square_statuses {
+            text.push('\n');
+            text.extend(colors.iter().map(|color| emoji(*color, state.color_blind)));
+        }
+    }
+
+    Some(text)
+}
+
+fn emoji(status: LetterStatus, color_blind: bool) -> char {
+    match (status, color_blind) {
+        (LetterStatus::Correct, false) => '🟩',
+        (LetterStatus::Present, false) => '🟨',
+        (LetterStatus::Correct, true) => '🟧',
+        (LetterStatus::Present, true) => '🟦',
+        _ => '⬛',
+    }
+}
+
+/// Prints the result, and writes it to a file and the clipboard if asked to. This has to happen
+/// after the terminal has been restored, or it would be drawn over.
+pub fn publish(text: &str, options: &ShareOptions) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    writeln!(stdout, "{}", text)?;
+
+    if options.clipboard {
+        // Terminals that understand OSC 52 put the text on the clipboard, even over SSH, and
+        // the rest ignore it
+        write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    }
+    stdout.flush()?;
+
+    if let Some(path) = &options.file {
+        fs::write(path, format!("{}\n", text))?;
+    }
+
+    Ok(())
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | u32::from(*byte) << (16 - 8 * i));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::Board;
+
+    fn finished_game(guesses: &[&str], solution: &str) -> AppState {
+        let mut state = AppState::with_solution(solution);
+
+        for guess in guesses {
+            state.guesses.push(guess.to_string());
+            state.check_word_status();
+        }
+        if guesses.last() == Some(&solution) {
+            state.game_won();
+        } else {
+            state.game_lost();
+        }
+
+        state
+    }
+
+    #[test]
+    fn share_text_hides_the_letters() {
+        let mut state = finished_game(&["geese", "those"], "those");
+        state.puzzle_number = Some(412);
+
+        assert_eq!(
+            share_text(&state).unwrap(),
+            "Rustle #412 2/6\n\n⬛⬛⬛🟩🟩\n🟩🟩🟩🟩🟩"
+        );
+    }
+
+    #[test]
+    fn share_text_marks_hard_mode_and_color_blind_games() {
+        let mut state = finished_game(&["ethos", "those"], "those");
+        state.hard_mode = true;
+        state.color_blind = true;
+
+        assert_eq!(
+            share_text(&state).unwrap(),
+            "Rustle 2/6*\n\n🟦🟦🟦🟦🟦\n🟧🟧🟧🟧🟧"
+        );
+    }
+
+    #[test]
+    fn lost_games_score_an_x() {
+        let state = finished_game(&["geese"], "those");
+
+        assert!(share_text(&state).unwrap().starts_with("Rustle X/6\n"));
+        assert_eq!(share_text(&AppState::default()), None);
+    }
+
+    #[test]
+    fn share_text_shows_every_board() {
+        let mut state = AppState {
+            boards: vec![
+                Board::new(String::from("those")),
+                Board::new(String::from("geese")),
+            ],
+            max_attempts: Some(7),
+            ..AppState::default()
+        };
+        for guess in ["those", "geese"] {
+            state.guesses.push(String::from(guess));
+            state.check_word_status();
+        }
+        state.game_won();
+
+        assert_eq!(
+            share_text(&state).unwrap(),
+            "Rustle x2 2/7\n\n🟩🟩🟩🟩🟩\n\n⬛⬛⬛🟩🟩\n🟩🟩🟩🟩🟩"
+        );
+    }
+
+    #[test]
+    fn share_text_shows_the_attempt_limit() {
+        let mut state = finished_game(&["those"], "those");
+
+        state.max_attempts = Some(8);
+        assert!(share_text(&state).unwrap().starts_with("Rustle 1/8\n"));
+
+        state.max_attempts = None;
+        assert!(share_text(&state).unwrap().starts_with("Rustle 1/∞\n"));
+    }
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64("🟩".as_bytes()), "8J+fqQ==");
+    }
+}
@@ -0,0 +1,440 @@
+use std::sync::OnceLock;
+
+use super::state::LetterStatus;
+use super::words::WordList;
+
+/// The colours a guess gets against a word, packed into a number with a base 3 digit per letter:
+/// 0 for a letter that isn't in the word, 1 for yellow and 2 for green
+pub type Pattern = u32;
+
+/// Works out which answers are still possible and which guess would narrow them down the most
+pub struct Solver {
+    answers: Vec<Vec<u8>>,
+    /// Every allowed guess, in alphabetical order
+    guesses: Vec<Vec<u8>>,
+    word_length: usize,
+    /// Ranking every guess against the full answer list is by far the slowest thing the solver
+    /// does, and it's the same for every game with these words, so it's only done once
+    opening: OnceLock<Vec<Suggestion>>,
+    /// The colours of every guess against every answer, a row of answers for each guess, worked
+    /// out the first time they're needed. Only words of up to `MAX_TABLE_LENGTH` letters have
+    /// few enough patterns to fit in a byte each, and longer ones are scored as they come up.
+    patterns: OnceLock<Option<Vec<u8>>>,
+}
+
+/// The longest words whose patterns fit in a byte: 3^5 is 243
+const MAX_TABLE_LENGTH: usize = 5;
+
+/// A possible next guess and how much it's expected to tell
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    /// The expected information from the guess's colours, in bits
+    pub entropy: f64,
+    /// Whether the guess could be the answer itself
+    pub candidate: bool,
+}
+
+/// How one guess of a finished game measured up to what the solver would have played
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuessAnalysis {
+    pub guess: String,
+    /// How many answers were still possible before the guess
+    pub before: usize,
+    /// How many answers were still possible after its colours were revealed
+    pub after: usize,
+    /// The information the guess's colours actually gave, in bits
+    pub bits: f64,
+    /// The information the guess was expected to give before its colours were known
+    pub expected: f64,
+    /// The guess the solver would have played instead, with the information it expected
+    pub best: Suggestion,
+}
+
+/// What the hint panel shows
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hints {
+    /// How many answers still fit every guess so far
+    pub candidates: usize,
+    /// The best guesses to play next, best first
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Solver {
+    pub fn new(words: &WordList) -> Solver {
+        let mut guesses: Vec<Vec<u8>> = words
+            .allowed()
+            .map(|word| word.as_bytes().to_vec())
+            .collect();
+        guesses.sort();
+
+        Solver {
+            answers: words
+                .answers()
+                .iter()
+                .map(|word| word.as_bytes().to_vec())
+                .collect(),
+            word_length: guesses.first().map_or(0, Vec::len),
+            guesses,
+            opening: OnceLock::new(),
+            patterns: OnceLock::new(),
+        }
+    }
+
+    /// Every answer that would have given each guess the colours it got. `rows` may be shorter
+    /// than `guesses`, like on a board that was solved before the last few guesses.
+    pub fn candidates(&self, guesses: &[String], rows: &[Vec<LetterStatus>]) -> Vec<&str> {
+        self.remaining(guesses, rows)
+            .into_iter()
+            .map(|i| word(&self.answers[i]))
+            .collect()
+    }
+
+    /// Every allowed guess, ranked by how much it's expected to narrow down what's left
+    pub fn suggestions(&self, guesses: &[String], rows: &[Vec<LetterStatus>]) -> Vec<Suggestion> {
+        if rows.is_empty() {
+            return self
+                .opening
+                .get_or_init(|| self.rank(&self.all_answers()))
+                .clone();
+        }
+
+        self.rank(&self.remaining(guesses, rows))
+    }
+
+    /// The remaining candidates and the best `count` guesses to play next
+    pub fn hints(&self, guesses: &[String], rows: &[Vec<LetterStatus>], count: usize) -> Hints {
+        let mut suggestions = self.suggestions(guesses, rows);
+        suggestions.truncate(count);
+
+        Hints {
+            candidates: self.remaining(guesses, rows).len(),
+            suggestions,
+        }
+    }
+
+    /// Goes through a game row by row, comparing each guess with the solver's pick at that point.
+    /// Whatever the guess got above what it expected was luck, and whatever it expected below
+    /// the solver's pick was skill.
+    pub fn analyse(&self, guesses: &[String], rows: &[Vec<LetterStatus>]) -> Vec<GuessAnalysis> {
+        (0..rows.len().min(guesses.len()))
+            .map(|i| {
+                let before = self.remaining(&guesses[..i], &rows[..i]).len();
+                let after = self.remaining(&guesses[..=i], &rows[..=i]).len();
+                let ranking = self.suggestions(&guesses[..i], &rows[..i]);
+                let expected = ranking
+                    .iter()
+                    .find(|suggestion| suggestion.word == guesses[i])
+                    .map_or(0.0, |suggestion| suggestion.entropy);
+
+                GuessAnalysis {
+                    guess: guesses[i].clone(),
+                    before,
+                    after,
+                    bits: if after > 0 {
+                        (before as f64 / after as f64).log2()
+                    } else {
+                        0.0
+                    },
+                    expected,
+                    best: ranking[0].clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn all_answers(&self) -> Vec<usize> {
+        (0..self.answers.len()).collect()
+    }
+
+    /// The table of every guess's pattern against every answer, if the words are short enough
+    /// to have one
+    fn table(&self) -> Option<&[u8]> {
+        self.patterns
+            .get_or_init(|| {
+                (self.word_length <= MAX_TABLE_LENGTH).then(|| {
+                    self.guesses
+                        .iter()
+                        .flat_map(|guess| {
+                            self.answers
+                                .iter()
+                                .map(move |answer| score(guess, answer) as u8)
+                        })
+                        .collect()
+                })
+            })
+            .as_deref()
+    }
+
+    /// The colours the guess with this index gets against the answer with this one
+    fn pattern(&self, table: Option<&[u8]>, guess: usize, answer: usize) -> Pattern {
+        match table {
+            Some(table) => Pattern::from(table[guess * self.answers.len() + answer]),
+            None => score(&self.guesses[guess], &self.answers[answer]),
+        }
+    }
+
+    /// The indexes of the answers that fit every row
+    fn remaining(&self, guesses: &[String], rows: &[Vec<LetterStatus>]) -> Vec<usize> {
+        let table = self.table();
+        let rows: Vec<(&[u8], Option<usize>, Pattern)> = guesses
+            .iter()
+            .zip(rows)
+            .map(|(guess, colors)| {
+                let index = self
+                    .guesses
+                    .binary_search_by(|allowed| allowed.as_slice().cmp(guess.as_bytes()))
+                    .ok();
+                (guess.as_bytes(), index, pattern_of(colors))
+            })
+            .collect();
+
+        (0..self.answers.len())
+            .filter(|answer| {
+                rows.iter().all(|(guess, index, pattern)| {
+                    // A guess that isn't in the list, like a word from the other player in a hot
+                    // seat game, isn't in the table either
+                    let got = match index {
+                        Some(index) => self.pattern(table, *index, *answer),
+                        None => score(guess, &self.answers[*answer]),
+                    };
+                    got == *pattern
+                })
+            })
+            .collect()
+    }
+
+    /// Ranks every guess by the entropy of the colours it would get against the remaining
+    /// answers. Guesses that could be the answer win ties, since they might end the game.
+    fn rank(&self, remaining: &[usize]) -> Vec<Suggestion> {
+        let table = self.table();
+        let mut counts = vec![0u32; 3usize.pow(self.word_length as u32)];
+        let mut seen: Vec<Pattern> = Vec::new();
+
+        let mut suggestions: Vec<Suggestion> = self
+            .guesses
+            .iter()
+            .enumerate()
+            .map(|(index, guess)| {
+                for answer in remaining {
+                    let pattern = self.pattern(table, index, *answer);
+                    if counts[pattern as usize] == 0 {
+                        seen.push(pattern);
+                    }
+                    counts[pattern as usize] += 1;
+                }
+
+                let total = remaining.len() as f64;
+                let entropy = seen
+                    .drain(..)
+                    .map(|pattern| {
+                        let p = f64::from(std::mem::take(&mut counts[pattern as usize])) / total;
+                        -p * p.log2()
+                    })
+                    .sum::<f64>()
+                    // Rounding would otherwise break ties between guesses that split the
+                    // answers the same way
+                    .max(0.0);
+
+                Suggestion {
+                    word: word(guess).to_string(),
+                    entropy,
+                    candidate: remaining
+                        .iter()
+                        .any(|answer| self.answers[*answer] == *guess),
+                }
+            })
+            .collect();
+
+        // Sorting is stable, so equally good guesses stay in alphabetical order
+        suggestions.sort_by(|a, b| {
+            b.entropy
+                .total_cmp(&a.entropy)
+                .then(b.candidate.cmp(&a.candidate))
+        });
+
+        suggestions
+    }
+}
+
+/// Packs a row of colours from `AppState` into a `Pattern`
+pub fn pattern_of(colors: &[LetterStatus]) -> Pattern {
+    colors.iter().rev().fold(0, |pattern, color| {
+        let digit = match color {
+            LetterStatus::Correct => 2,
+            LetterStatus::Present => 1,
+            _ => 0,
+        };
+
+        pattern * 3 + digit
+    })
+}
+
+/// The same scoring as `score_guess`, without allocating, since the solver does it millions of
+/// times
+fn score(guess: &[u8], solution: &[u8]) -> Pattern {
+    let mut digits = [0; 16];
+    let mut unmatched = [0u8; 26];
+
+    for ((digit, letter), solution_letter) in digits.iter_mut().zip(guess).zip(solution) {
+        if letter == solution_letter {
+            *digit = 2;
+        } else {
+            unmatched[usize::from(solution_letter - b'a')] += 1;
+        }
+    }
+
+    for (digit, letter) in digits.iter_mut().zip(guess) {
+        let count = &mut unmatched[usize::from(letter - b'a')];
+        if *digit == 0 && *count > 0 {
+            *count -= 1;
+            *digit = 1;
+        }
+    }
+
+    digits[..guess.len()]
+        .iter()
+        .rev()
+        .fold(0, |pattern, digit| pattern * 3 + digit)
+}
+
+fn word(letters: &[u8]) -> &str {
+    // Word lists only ever have the letters a to z in them
+    std::str::from_utf8(letters).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::score_guess;
+
+    fn words(answers: &[&str], allowed: &[&str]) -> WordList {
+        WordList::new(
+            answers.iter().map(|word| word.to_string()).collect(),
+            allowed.iter().map(|word| word.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn scoring_matches_the_game() {
+        let words = WordList::builtin();
+        let sample: Vec<&String> = words.answers().iter().step_by(37).collect();
+
+        for guess in &sample {
+            for solution in &sample {
+                assert_eq!(
+                    score(guess.as_bytes(), solution.as_bytes()),
+                    pattern_of(&score_guess(guess, solution)),
+                    "{} against {}",
+                    guess,
+                    solution
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn candidates_fit_every_guess() {
+        let solver = Solver::new(&words(&["crane", "crate", "trace", "those"], &[]));
+        let guesses = vec![String::from("react")];
+        let rows = vec![score_guess("react", "crate")];
+
+        assert_eq!(solver.candidates(&guesses, &rows), vec!["crate"]);
+        assert_eq!(solver.candidates(&[], &[]).len(), 4);
+    }
+
+    #[test]
+    fn guesses_that_split_the_answers_are_ranked_first() {
+        // "dumpy" can't tell any of the answers apart, while "hblzz" splits them up completely
+        let solver = Solver::new(&words(
+            &["batch", "catch", "hatch", "latch"],
+            &["dumpy", "hblzz"],
+        ));
+
+        let hints = solver.hints(&[], &[], 2);
+
+        assert_eq!(hints.candidates, 4);
+        assert_eq!(hints.suggestions.len(), 2);
+        assert_eq!(hints.suggestions[0].word, "hblzz");
+        assert!((hints.suggestions[0].entropy - 2.0).abs() < 1e-9);
+        // The answers themselves only ever pick out one of the four
+        assert!((hints.suggestions[1].entropy - 0.811).abs() < 1e-3);
+        let ranking = solver.suggestions(&[], &[]);
+        assert_eq!(ranking.last().unwrap().word, "dumpy");
+        assert_eq!(ranking.last().unwrap().entropy, 0.0);
+    }
+
+    #[test]
+    fn possible_answers_win_ties() {
+        let solver = Solver::new(&words(&["batch", "catch"], &["about", "bacon"]));
+        let guesses = vec![String::from("hatch")];
+        let rows = vec![score_guess("hatch", "catch")];
+
+        // Every one of these tells the two answers apart, but only two could be right
+        let ranking: Vec<String> = solver
+            .suggestions(&guesses, &rows)
+            .into_iter()
+            .map(|suggestion| suggestion.word)
+            .collect();
+        assert_eq!(ranking[..2], ["batch", "catch"]);
+    }
+
+    #[test]
+    fn analysis_compares_every_row_with_the_best_guess() {
+        let solver = Solver::new(&words(
+            &["batch", "catch", "hatch", "latch"],
+            &["dumpy", "hblzz"],
+        ));
+        let guesses: Vec<String> = ["dumpy", "catch", "batch"].map(String::from).to_vec();
+        let rows: Vec<Vec<LetterStatus>> = guesses
+            .iter()
+            .map(|guess| score_guess(guess, "batch"))
+            .collect();
+
+        let analysis = solver.analyse(&guesses, &rows);
+
+        assert_eq!(analysis.len(), 3);
+        assert_eq!((analysis[0].before, analysis[0].after), (4, 4));
+        assert_eq!(analysis[0].bits, 0.0);
+        assert_eq!(analysis[0].expected, 0.0);
+        assert_eq!(analysis[0].best.word, "hblzz");
+        // "catch" only rules itself out
+        assert_eq!((analysis[1].before, analysis[1].after), (4, 3));
+        assert!((analysis[1].bits - (4.0f64 / 3.0).log2()).abs() < 1e-9);
+        assert!(analysis[1].expected < analysis[1].best.entropy);
+        assert_eq!((analysis[2].before, analysis[2].after), (3, 1));
+    }
+
+    #[test]
+    fn looked_up_patterns_match_the_scoring() {
+        let sample: Vec<String> = WordList::builtin()
+            .answers()
+            .iter()
+            .step_by(37)
+            .cloned()
+            .collect();
+        let solver = Solver::new(&WordList::new(sample.clone(), Vec::new()));
+        let table = solver.table().unwrap();
+        assert_eq!(table.len(), sample.len() * sample.len());
+
+        for guess in 0..solver.guesses.len() {
+            for answer in 0..solver.answers.len() {
+                assert_eq!(
+                    solver.pattern(Some(table), guess, answer),
+                    score(&solver.guesses[guess], &solver.answers[answer])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn longer_words_are_scored_without_a_table() {
+        let solver = Solver::new(&words(&["planet", "plants", "planes"], &["stomps"]));
+        let guesses = vec![String::from("stomps")];
+        let rows = vec![score_guess("stomps", "planes")];
+
+        assert!(solver.table().is_none());
+        assert_eq!(solver.candidates(&guesses, &rows), vec!["planes"]);
+        assert_eq!(solver.hints(&guesses, &rows, 1).candidates, 1);
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use super::state::Stopwatch;
+
+/// As many words as can be solved one after another before the time is up
+#[derive(Debug, Clone)]
+pub struct Speedrun {
+    pub limit: Duration,
+    /// Started with the first word and stopped once the run is over
+    pub clock: Stopwatch,
+    pub solved: u32,
+    /// The best speedrun of this length before this one, to tell whether it's been beaten
+    pub previous_best: Option<u32>,
+}
+
+impl Speedrun {
+    pub fn new(limit: Duration, previous_best: Option<u32>) -> Speedrun {
+        Speedrun {
+            limit,
+            clock: Stopwatch::starting_from(Duration::ZERO),
+            solved: 0,
+            previous_best,
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.limit.saturating_sub(self.clock.elapsed())
+    }
+
+    pub fn is_new_best(&self) -> bool {
+        self.solved > self.previous_best.unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_clock_runs_down_to_zero() {
+        let mut speedrun = Speedrun::new(Duration::from_secs(60), None);
+        speedrun.clock = Stopwatch::starting_from(Duration::from_secs(45));
+        let remaining = speedrun.remaining();
+        assert!(remaining <= Duration::from_secs(15) && remaining > Duration::from_secs(14));
+
+        speedrun.clock = Stopwatch::starting_from(Duration::from_secs(75));
+        assert_eq!(speedrun.remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn only_beating_the_last_best_is_a_new_best() {
+        let mut speedrun = Speedrun::new(Duration::from_secs(60), Some(2));
+        assert!(!speedrun.is_new_best());
+
+        speedrun.solved = 2;
+        assert!(!speedrun.is_new_best());
+        speedrun.solved = 3;
+        assert!(speedrun.is_new_best());
+
+        let first = Speedrun::new(Duration::from_secs(60), None);
+        assert!(!first.is_new_best());
+    }
+}
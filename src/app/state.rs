@@ -1,106 +1,356 @@
-use crate::app::words;
+use crate::app::absurdle;
+use crate::app::options::{GameOptions, DEFAULT_MAX_ATTEMPTS};
+use crate::app::words::{WordList, DEFAULT_WORD_LENGTH};
 use std::collections::HashMap;
-use tui::style::Color;
+use std::time::{Duration, Instant};
 
 pub struct AppState {
     pub input: String,
-    pub solution: String,
     pub guesses: Vec<String>,
-    pub square_colors: Vec<Vec<Color>>,
-    pub keyboard_colors: HashMap<char, Color>,
-    pub attempt: u8,
+    /// Every hidden word being played at once, with one guess going to all of them
+    pub boards: Vec<Board>,
+    pub attempt: usize,
+    /// How many guesses the player gets, or `None` to keep going until they get it
+    pub max_attempts: Option<usize>,
     pub game_status: GameStatus,
+    /// How many letters the solution and every guess have
+    pub word_length: usize,
+    /// Whether revealed hints must be used in every guess
+    pub hard_mode: bool,
+    /// Whether the shared result uses orange and blue squares instead of green and yellow
+    pub color_blind: bool,
+    /// The daily puzzle being played, if any
+    pub puzzle_number: Option<u32>,
+    /// Feedback for the last rejected guess
+    pub message: Option<String>,
+    /// In Absurdle, every word that still fits all the feedback given so far
+    pub candidates: Option<Vec<String>>,
+    /// How long the game has been going, stopped once it's over
+    pub stopwatch: Stopwatch,
+}
+
+/// Time spent on a game. A resumed game carries on from the time it was saved with.
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch {
+    earlier: Duration,
+    running_since: Option<Instant>,
+}
+
+impl Stopwatch {
+    pub fn starting_from(earlier: Duration) -> Stopwatch {
+        Stopwatch {
+            earlier,
+            running_since: Some(Instant::now()),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.earlier
+            + self
+                .running_since
+                .map_or(Duration::ZERO, |since| since.elapsed())
+    }
+
+    pub fn stop(&mut self) {
+        self.earlier = self.elapsed();
+        self.running_since = None;
+    }
+}
+
+/// What a guess revealed about one of its letters. How that looks is up to the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LetterStatus {
+    /// In the word, in that spot
+    Correct,
+    /// In the word, but somewhere else
+    Present,
+    /// Not in the word, or not as many times as it was guessed
+    Absent,
+    /// Not guessed yet, which only a key can be
+    Unknown,
+}
+
+/// One hidden word, along with the colours every guess has been given against it
+pub struct Board {
+    pub solution: String,
+    /// A row of colours for each guess made while the board was unsolved
+    pub square_statuses: Vec<Vec<LetterStatus>>,
+    /// The most each letter has been revealed to be, for the keyboard
+    pub key_statuses: HashMap<char, LetterStatus>,
+    /// How many guesses it took to solve the board, once it's solved
+    pub solved_at: Option<usize>,
+}
+
+impl Board {
+    pub fn new(solution: String) -> Board {
+        Board {
+            solution,
+            square_statuses: Vec::new(),
+            solved_at: None,
+            key_statuses: ('a'..='z')
+                .map(|letter| (letter, LetterStatus::Unknown))
+                .collect(),
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.solved_at.is_some()
+    }
+
+    /// Colours a guess against this board's solution. A solved board doesn't take any more rows.
+    pub fn check_word_status(&mut self, guess: &str) {
+        if self.is_solved() {
+            return;
+        }
+
+        let colors = score_guess(guess, &self.solution);
+        self.record(guess, colors);
+    }
+
+    /// Adds a row of colours for a guess and updates the keyboard to match. The board counts as
+    /// solved once a row comes back all green.
+    pub fn record(&mut self, guess: &str, colors: Vec<LetterStatus>) {
+        for (letter, color) in guess.chars().zip(colors.iter()) {
+            // A letter that shows up twice in a guess can be both green and grey, so the key
+            // only ever gets upgraded and never loses what an earlier tile revealed.
+            if let Some(val) = self.key_statuses.get_mut(&letter) {
+                if key_rank(*color) > key_rank(*val) {
+                    *val = *color;
+                }
+            }
+        }
+
+        let solved = colors.iter().all(|color| *color == LetterStatus::Correct);
+        self.square_statuses.push(colors);
+        if solved {
+            self.solved_at = Some(self.square_statuses.len());
+        }
+    }
 }
 
 impl Default for AppState {
     fn default() -> Self {
         AppState {
             input: String::new(),
-            solution: words::random_word(),
+            boards: vec![Board::new(WordList::builtin().random_word())],
             attempt: 0,
-            square_colors: Vec::new(),
+            max_attempts: Some(DEFAULT_MAX_ATTEMPTS),
             guesses: Vec::new(),
             game_status: GameStatus::InProgress,
-            keyboard_colors: HashMap::from([
-                ('a', Color::Reset),
-                ('b', Color::Reset),
-                ('c', Color::Reset),
-                ('d', Color::Reset),
-                ('e', Color::Reset),
-                ('f', Color::Reset),
-                ('g', Color::Reset),
-                ('h', Color::Reset),
-                ('i', Color::Reset),
-                ('j', Color::Reset),
-                ('k', Color::Reset),
-                ('l', Color::Reset),
-                ('m', Color::Reset),
-                ('n', Color::Reset),
-                ('o', Color::Reset),
-                ('p', Color::Reset),
-                ('q', Color::Reset),
-                ('r', Color::Reset),
-                ('s', Color::Reset),
-                ('t', Color::Reset),
-                ('u', Color::Reset),
-                ('v', Color::Reset),
-                ('w', Color::Reset),
-                ('x', Color::Reset),
-                ('y', Color::Reset),
-                ('z', Color::Reset),
-            ]),
+            word_length: DEFAULT_WORD_LENGTH,
+            hard_mode: false,
+            color_blind: false,
+            puzzle_number: None,
+            message: None,
+            candidates: None,
+            stopwatch: Stopwatch::starting_from(Duration::ZERO),
         }
     }
 }
 
 impl AppState {
-    pub fn new() -> AppState {
-        AppState::default()
+    pub fn new(options: &GameOptions) -> AppState {
+        if options.absurdle {
+            return AppState::absurdle(options);
+        }
+
+        let solutions = match (&options.word, options.puzzle) {
+            (Some(word), _) => vec![word.clone()],
+            // Each board gets its own day's worth of words, so a single board daily puzzle is
+            // the same word no matter how many boards anyone else plays
+            (None, Some(puzzle)) => (0..options.boards)
+                .map(|i| {
+                    let index = puzzle as usize * options.boards + i;
+                    options.words.daily_word(index as u32)
+                })
+                .collect(),
+            (None, None) => options.words.random_words(options.boards),
+        };
+
+        AppState {
+            boards: solutions.into_iter().map(Board::new).collect(),
+            word_length: options.word_length,
+            max_attempts: options.max_attempts,
+            hard_mode: options.hard_mode,
+            color_blind: options.color_blind,
+            puzzle_number: options.puzzle,
+            ..AppState::default()
+        }
+    }
+
+    /// A game with no solution at all. Every guess is answered with whatever keeps the most
+    /// words in play, and the word is only settled once there's nothing else left.
+    fn absurdle(options: &GameOptions) -> AppState {
+        AppState {
+            boards: vec![Board::new(String::new())],
+            word_length: options.word_length,
+            max_attempts: options.max_attempts,
+            hard_mode: options.hard_mode,
+            color_blind: options.color_blind,
+            candidates: Some(options.words.answers().to_vec()),
+            ..AppState::default()
+        }
+    }
+
+    /// A game with a single board and the given solution
+    pub fn with_solution(solution: &str) -> AppState {
+        AppState {
+            boards: vec![Board::new(solution.to_string())],
+            ..AppState::default()
+        }
     }
 
     pub fn game_lost(&mut self) {
-        self.game_status = GameStatus::Lose
+        self.game_status = GameStatus::Lose;
+        self.stopwatch.stop();
     }
 
     pub fn game_won(&mut self) {
-        self.game_status = GameStatus::Win
+        self.game_status = GameStatus::Win;
+        self.stopwatch.stop();
     }
 
     pub fn increment_attempt(&mut self) {
         self.attempt += 1
     }
 
-    pub fn check_word_status(&mut self) {
-        let mut colors = Vec::new();
-
-        if let Some(last_guess) = self.guesses.last() {
-            // We'll be a bit lazy here and hardcode the word length (5) here.
-            for i in 0..5 {
-                let guess_letter = last_guess.chars().nth(i);
-
-                if guess_letter == self.solution.chars().nth(i) {
-                    if let Some(val) = self.keyboard_colors.get_mut(&guess_letter.unwrap()) {
-                        *val = Color::Green;
-                    }
-
-                    colors.push(Color::Green)
-                } else if self.solution.contains(guess_letter.unwrap()) {
-                    if let Some(val) = self.keyboard_colors.get_mut(&guess_letter.unwrap()) {
-                        *val = Color::Yellow;
-                    }
-
-                    colors.push(Color::Yellow)
-                } else {
-                    if let Some(val) = self.keyboard_colors.get_mut(&guess_letter.unwrap()) {
-                        *val = Color::Black;
-                    }
-
-                    colors.push(Color::Reset)
+    /// Whether the player has used up every guess they had
+    pub fn out_of_attempts(&self) -> bool {
+        self.max_attempts
+            .is_some_and(|max_attempts| self.attempt >= max_attempts)
+    }
+
+    pub fn all_solved(&self) -> bool {
+        self.boards.iter().all(Board::is_solved)
+    }
+
+    /// Checks a guess against the hints revealed so far, returning why it breaks the hard mode
+    /// rules if it does. Green letters have to stay in place and every yellow letter has to be
+    /// used somewhere. Outside of hard mode every guess is fine.
+    pub fn hard_mode_violation(&self, guess: &str) -> Option<String> {
+        if !self.hard_mode {
+            return None;
+        }
+
+        let guess: Vec<char> = guess.chars().collect();
+        let rows = self
+            .boards
+            .iter()
+            .flat_map(|board| self.guesses.iter().zip(board.square_statuses.iter()));
+
+        for (previous, colors) in rows {
+            for (i, (letter, color)) in previous.chars().zip(colors.iter()).enumerate() {
+                if *color == LetterStatus::Correct && guess.get(i) != Some(&letter) {
+                    return Some(format!(
+                        "{} letter must be {}",
+                        ordinal(i + 1),
+                        letter.to_ascii_uppercase()
+                    ));
                 }
             }
+
+            // A yellow or green letter revealed twice has to be used twice
+            let mut revealed: HashMap<char, usize> = HashMap::new();
+            for (letter, color) in previous.chars().zip(colors.iter()) {
+                if *color == LetterStatus::Correct || *color == LetterStatus::Present {
+                    *revealed.entry(letter).or_insert(0) += 1;
+                }
+            }
+
+            for (letter, color) in previous.chars().zip(colors.iter()) {
+                let used = guess.iter().filter(|c| **c == letter).count();
+
+                if *color == LetterStatus::Present && used < revealed[&letter] {
+                    return Some(format!(
+                        "Guess must contain {}",
+                        letter.to_ascii_uppercase()
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Colours the last guess on every board that hasn't been solved yet
+    pub fn check_word_status(&mut self) {
+        let Some(last_guess) = self.guesses.last() else {
+            return;
+        };
+
+        if let Some(candidates) = &mut self.candidates {
+            let board = &mut self.boards[0];
+            let (colors, remaining) = absurdle::dodge(last_guess, candidates);
+            *candidates = remaining;
+
+            if let [solution] = candidates.as_slice() {
+                board.solution = solution.clone();
+            }
+            board.record(last_guess, colors);
+            return;
         }
 
-        self.square_colors.push(colors)
+        for board in self.boards.iter_mut() {
+            board.check_word_status(last_guess);
+        }
+    }
+}
+
+/// Scores a guess against the solution the same way Wordle does.
+///
+/// Greens are handed out first. Yellows are then given from left to right, but only while there
+/// are copies of that letter in the solution that haven't already been matched, so guessing
+/// "geese" against "those" only lights up the final "e".
+pub fn score_guess(guess: &str, solution: &str) -> Vec<LetterStatus> {
+    let guess: Vec<char> = guess.chars().collect();
+    let solution: Vec<char> = solution.chars().collect();
+    let mut colors = vec![LetterStatus::Absent; guess.len()];
+    let mut unmatched: HashMap<char, usize> = HashMap::new();
+
+    for (i, letter) in guess.iter().enumerate() {
+        if solution.get(i) == Some(letter) {
+            colors[i] = LetterStatus::Correct;
+        } else if let Some(solution_letter) = solution.get(i) {
+            *unmatched.entry(*solution_letter).or_insert(0) += 1;
+        }
+    }
+
+    for (i, letter) in guess.iter().enumerate() {
+        if colors[i] == LetterStatus::Correct {
+            continue;
+        }
+
+        if let Some(count) = unmatched.get_mut(letter) {
+            if *count > 0 {
+                *count -= 1;
+                colors[i] = LetterStatus::Present;
+            }
+        }
+    }
+
+    colors
+}
+
+/// 1st, 2nd, 3rd and so on, for telling the player which letter is wrong
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{}{}", n, suffix)
+}
+
+/// How much a key's status tells the player, so a key is never downgraded by a later guess
+fn key_rank(status: LetterStatus) -> u8 {
+    match status {
+        LetterStatus::Correct => 3,
+        LetterStatus::Present => 2,
+        LetterStatus::Absent => 1,
+        LetterStatus::Unknown => 0,
     }
 }
 
@@ -114,31 +364,204 @@ pub enum GameStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
+
+    use LetterStatus::{Absent as A, Correct as C, Present as P};
 
     #[test]
     fn check_status_works() {
+        // (solution, guess, expected colours)
+        let cases = [
+            ("rebus", "route", [C, A, P, A, P]),
+            ("those", "geese", [A, A, A, C, C]),
+            ("abide", "speed", [A, A, P, A, P]),
+            ("kebab", "abbey", [P, P, C, P, A]),
+            ("rower", "error", [P, P, A, P, C]),
+            ("hello", "lolly", [A, P, C, C, A]),
+            ("llama", "mamma", [A, P, A, C, C]),
+            ("crane", "crane", [C, C, C, C, C]),
+            ("eerie", "sheep", [A, A, P, P, A]),
+        ];
+
+        for (solution, guess, expected) in cases {
+            let mut board = Board::new(String::from(solution));
+            board.square_statuses.push(vec![P, A, A, P, P]);
+            let mut test_state = AppState {
+                guesses: vec![String::from(guess)],
+                boards: vec![board],
+                ..AppState::default()
+            };
+
+            test_state.check_word_status();
+
+            assert_eq!(
+                test_state.boards[0].square_statuses.last().unwrap(),
+                &expected.to_vec(),
+                "{} against {}",
+                guess,
+                solution
+            );
+        }
+    }
+
+    #[test]
+    fn key_statuses_are_never_downgraded() {
+        let mut test_state = AppState::with_solution("those");
+
+        // The last "e" is green while the first two are grey
+        test_state.guesses.push(String::from("geese"));
+        test_state.check_word_status();
+        assert_eq!(
+            test_state.boards[0].key_statuses[&'e'],
+            LetterStatus::Correct
+        );
+        assert_eq!(
+            test_state.boards[0].key_statuses[&'g'],
+            LetterStatus::Absent
+        );
+
+        // "e" is only yellow in this guess, and "h" is yellow before it turns green
+        test_state.guesses.push(String::from("ethos"));
+        test_state.check_word_status();
+        assert_eq!(
+            test_state.boards[0].key_statuses[&'e'],
+            LetterStatus::Correct
+        );
+        assert_eq!(
+            test_state.boards[0].key_statuses[&'h'],
+            LetterStatus::Present
+        );
+
+        test_state.guesses.push(String::from("those"));
+        test_state.check_word_status();
+        assert_eq!(
+            test_state.boards[0].key_statuses[&'h'],
+            LetterStatus::Correct
+        );
+    }
+
+    #[test]
+    fn hard_mode_enforces_revealed_hints() {
         let mut test_state = AppState {
-        solution: String::from("rebus"),
-        guesses: vec![String::from("route")],
-        square_colors: vec![vec![
-            Color::Yellow,
-            Color::Reset,
-            Color::Reset,
-            Color::Yellow,
-            Color::Yellow,
-        ]],
-            ..AppState::default()
+            hard_mode: true,
+            ..AppState::with_solution("cider")
         };
 
+        // c, e and the last r are green, i is yellow
+        test_state.guesses.push(String::from("crier"));
         test_state.check_word_status();
-        let expected_result = vec![
-            Color::Green,
-            Color::Reset,
-            Color::Yellow,
-            Color::Reset,
-            Color::Yellow,
+
+        let cases = [
+            ("ember", Some("1st letter must be C")),
+            ("cobra", Some("4th letter must be E")),
+            ("caper", Some("Guess must contain I")),
+            ("crier", None),
+            ("cider", None),
         ];
 
-        assert_eq!(test_state.square_colors.last().unwrap(), &expected_result);
+        for (guess, expected) in cases {
+            assert_eq!(
+                test_state.hard_mode_violation(guess).as_deref(),
+                expected,
+                "{}",
+                guess
+            );
+        }
+    }
+
+    #[test]
+    fn hard_mode_counts_repeated_hints() {
+        let mut test_state = AppState {
+            hard_mode: true,
+            ..AppState::with_solution("geese")
+        };
+
+        // Two e's are green and one is yellow, so all three have to be reused
+        test_state.guesses.push(String::from("eerie"));
+        test_state.check_word_status();
+        assert_eq!(
+            test_state.boards[0].square_statuses.last().unwrap(),
+            &vec![P, C, A, A, C]
+        );
+
+        assert_eq!(
+            test_state.hard_mode_violation("hence").as_deref(),
+            Some("Guess must contain E")
+        );
+        assert_eq!(test_state.hard_mode_violation("geese"), None);
+    }
+
+    #[test]
+    fn ordinals_are_spelled_out() {
+        let ordinals: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21]
+            .iter()
+            .map(|n| ordinal(*n))
+            .collect();
+
+        assert_eq!(
+            ordinals,
+            ["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st"]
+        );
+    }
+
+    #[test]
+    fn unlimited_games_never_run_out_of_attempts() {
+        let mut test_state = AppState {
+            max_attempts: None,
+            attempt: 1000,
+            ..AppState::default()
+        };
+        assert!(!test_state.out_of_attempts());
+
+        test_state.max_attempts = Some(1000);
+        assert!(test_state.out_of_attempts());
+    }
+
+    #[test]
+    fn solved_boards_stop_taking_rows() {
+        let mut test_state = AppState {
+            boards: vec![
+                Board::new(String::from("those")),
+                Board::new(String::from("geese")),
+            ],
+            ..AppState::default()
+        };
+
+        for guess in ["those", "eerie", "geese"] {
+            test_state.guesses.push(String::from(guess));
+            test_state.check_word_status();
+        }
+
+        assert_eq!(test_state.boards[0].solved_at, Some(1));
+        assert_eq!(test_state.boards[0].square_statuses.len(), 1);
+        assert_eq!(test_state.boards[1].solved_at, Some(3));
+        assert_eq!(test_state.boards[1].square_statuses.len(), 3);
+        assert!(test_state.all_solved());
+    }
+
+    #[test]
+    fn absurdle_only_gives_in_when_nothing_is_left() {
+        let words = WordList::new(
+            ["crane", "crate", "those"].map(String::from).to_vec(),
+            Vec::new(),
+        );
+        let mut test_state = AppState::new(&GameOptions {
+            absurdle: true,
+            max_attempts: None,
+            words: Arc::new(words),
+            ..GameOptions::default()
+        });
+
+        for guess in ["crane", "crate"] {
+            test_state.guesses.push(String::from(guess));
+            test_state.check_word_status();
+            assert!(!test_state.all_solved(), "{} was allowed to win", guess);
+        }
+        assert_eq!(test_state.candidates, Some(vec![String::from("those")]));
+        assert_eq!(test_state.boards[0].solution, "those");
+
+        test_state.guesses.push(String::from("those"));
+        test_state.check_word_status();
+        assert_eq!(test_state.boards[0].solved_at, Some(3));
     }
 }
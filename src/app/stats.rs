@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::io::storage;
+
+/// How many attempts the guess distribution has room for, which is as many as the games that
+/// count towards the stats get
+pub const DISTRIBUTION_LEN: usize = 6;
+
+/// Everything we remember about the games a player has finished
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub played: u32,
+    pub wins: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    /// Wins by number of attempts, the first entry being wins on the first guess
+    pub distribution: Vec<u32>,
+    /// The most words solved in a speedrun, by how many seconds the speedrun was
+    pub speedrun_bests: BTreeMap<u64, u32>,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Stats {
+            played: 0,
+            wins: 0,
+            current_streak: 0,
+            max_streak: 0,
+            distribution: vec![0; DISTRIBUTION_LEN],
+            speedrun_bests: BTreeMap::new(),
+        }
+    }
+}
+
+impl Stats {
+    /// Loads stats from a file. A corrupt file is backed up and replaced with empty stats.
+    pub fn load(path: &Path) -> Result<Stats> {
+        storage::load_or_backup(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        storage::save(path, self)
+    }
+
+    pub fn record_win(&mut self, attempts: usize) {
+        self.played += 1;
+        self.wins += 1;
+        self.current_streak += 1;
+        self.max_streak = self.max_streak.max(self.current_streak);
+
+        if attempts == 0 {
+            return;
+        }
+        if self.distribution.len() < attempts {
+            self.distribution.resize(attempts, 0);
+        }
+        self.distribution[attempts - 1] += 1;
+    }
+
+    pub fn record_loss(&mut self) {
+        self.played += 1;
+        self.current_streak = 0;
+    }
+
+    pub fn speedrun_best(&self, limit: Duration) -> Option<u32> {
+        self.speedrun_bests.get(&limit.as_secs()).copied()
+    }
+
+    /// Speedruns only count towards the best for speedruns of the same length
+    pub fn record_speedrun(&mut self, limit: Duration, solved: u32) {
+        let best = self.speedrun_bests.entry(limit.as_secs()).or_insert(0);
+        *best = (*best).max(solved);
+    }
+
+    /// Rounded to the nearest whole percent, like the original
+    pub fn win_percentage(&self) -> u32 {
+        if self.played == 0 {
+            0
+        } else {
+            (f64::from(self.wins) * 100.0 / f64::from(self.played)).round() as u32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaks_and_distribution_are_tracked() {
+        let mut stats = Stats::default();
+
+        stats.record_win(3);
+        stats.record_win(4);
+        stats.record_loss();
+        stats.record_win(3);
+
+        assert_eq!(stats.played, 4);
+        assert_eq!(stats.wins, 3);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.max_streak, 2);
+        assert_eq!(stats.distribution, [0, 0, 2, 1, 0, 0]);
+        assert_eq!(stats.win_percentage(), 75);
+
+        stats.record_win(9);
+        assert_eq!(stats.distribution, [0, 0, 2, 1, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn speedrun_bests_are_kept_for_each_length() {
+        let mut stats = Stats::default();
+        let five_minutes = Duration::from_secs(300);
+
+        stats.record_speedrun(five_minutes, 4);
+        stats.record_speedrun(five_minutes, 2);
+        stats.record_speedrun(Duration::from_secs(60), 1);
+
+        assert_eq!(stats.speedrun_best(five_minutes), Some(4));
+        assert_eq!(stats.speedrun_best(Duration::from_secs(60)), Some(1));
+        assert_eq!(stats.speedrun_best(Duration::from_secs(120)), None);
+        // Other stats are left alone
+        assert_eq!(stats.played, 0);
+    }
+
+    #[test]
+    fn stats_survive_a_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+
+        assert_eq!(Stats::load(&path).unwrap(), Stats::default());
+
+        let mut stats = Stats::default();
+        stats.record_win(2);
+        stats.record_speedrun(Duration::from_secs(300), 3);
+        stats.save(&path).unwrap();
+
+        assert_eq!(Stats::load(&path).unwrap(), stats);
+    }
+
+    #[test]
+    fn corrupt_stats_are_backed_up_and_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+        std::fs::write(&path, "{ \"played\": 3, ").unwrap();
+
+        assert_eq!(Stats::load(&path).unwrap(), Stats::default());
+        assert!(!path.exists());
+
+        let backup = std::fs::read_to_string(storage::backup_path(&path)).unwrap();
+        assert_eq!(backup, "{ \"played\": 3, ");
+    }
+}
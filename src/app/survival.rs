@@ -0,0 +1,62 @@
+/// A run of words played from one pool of guesses. Only the guesses each word takes come out
+/// of the pool, and the run is over once it's empty.
+#[derive(Debug, Clone)]
+pub struct Survival {
+    /// Guesses left for the rest of the run, not counting the ones used on the word being played
+    pub pool: usize,
+    /// The most guesses any one word gets
+    pub per_word: usize,
+    pub solved: u32,
+    /// Guesses used on every word finished so far
+    pub used: usize,
+}
+
+impl Survival {
+    pub fn new(pool: usize, per_word: usize) -> Survival {
+        Survival {
+            pool,
+            per_word,
+            solved: 0,
+            used: 0,
+        }
+    }
+
+    /// How many guesses the next word gets
+    pub fn attempts(&self) -> usize {
+        self.pool.min(self.per_word)
+    }
+
+    pub fn word_over(&mut self, guesses: usize, solved: bool) {
+        self.pool = self.pool.saturating_sub(guesses);
+        self.used += guesses;
+        if solved {
+            self.solved += 1;
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.pool == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_guesses_carry_over() {
+        let mut survival = Survival::new(10, 6);
+        assert_eq!(survival.attempts(), 6);
+
+        survival.word_over(3, true);
+        assert_eq!((survival.pool, survival.attempts()), (7, 6));
+
+        survival.word_over(6, false);
+        assert_eq!((survival.pool, survival.attempts()), (1, 1));
+        assert!(!survival.is_over());
+
+        survival.word_over(1, true);
+        assert!(survival.is_over());
+        assert_eq!((survival.solved, survival.used), (2, 10));
+    }
+}
@@ -0,0 +1,242 @@
+use std::fs;
+use std::path::Path;
+
+use eyre::{eyre, Result, WrapErr};
+use serde::Deserialize;
+use tui::style::{Color, Style};
+
+use super::state::LetterStatus;
+
+/// The colours squares and keys are drawn in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub correct: Color,
+    pub present: Color,
+    pub absent: Color,
+    /// Keys that haven't been guessed yet, and empty squares
+    pub unknown: Color,
+    /// Letters on top of a correct, present or absent colour
+    pub text: Color,
+    /// Leaves out colour altogether, marking letters with brackets instead
+    pub monochrome: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            correct: Color::Green,
+            present: Color::Yellow,
+            absent: Color::DarkGray,
+            unknown: Color::Reset,
+            text: Color::Reset,
+            monochrome: false,
+        }
+    }
+}
+
+impl Theme {
+    /// Orange and blue instead of green and yellow, which are easier to tell apart for colour
+    /// blind players
+    pub fn color_blind() -> Theme {
+        Theme {
+            correct: Color::Rgb(245, 121, 58),
+            present: Color::Rgb(133, 192, 249),
+            ..Theme::default()
+        }
+    }
+
+    /// Bright colours with black letters on them
+    pub fn high_contrast() -> Theme {
+        Theme {
+            correct: Color::LightGreen,
+            present: Color::LightYellow,
+            absent: Color::Gray,
+            unknown: Color::Reset,
+            text: Color::Black,
+            monochrome: false,
+        }
+    }
+
+    /// No colours at all, for terminals without them and for NO_COLOR
+    pub fn monochrome() -> Theme {
+        Theme {
+            correct: Color::Reset,
+            present: Color::Reset,
+            absent: Color::Reset,
+            unknown: Color::Reset,
+            text: Color::Reset,
+            monochrome: true,
+        }
+    }
+
+    /// Reads a theme from a TOML file. Any colour the file leaves out is the default theme's.
+    pub fn load(path: &Path) -> Result<Theme> {
+        let contents = fs::read_to_string(path)
+            .wrap_err_with(|| format!("couldn't read {}", path.display()))?;
+
+        Theme::parse(&contents).wrap_err_with(|| format!("couldn't parse {}", path.display()))
+    }
+
+    fn parse(contents: &str) -> Result<Theme> {
+        let file: ThemeFile = toml::from_str(contents)?;
+        let mut theme = Theme::default();
+
+        for (color, name) in [
+            (&mut theme.correct, file.correct),
+            (&mut theme.present, file.present),
+            (&mut theme.absent, file.absent),
+            (&mut theme.unknown, file.unknown),
+            (&mut theme.text, file.text),
+        ] {
+            if let Some(name) = name {
+                *color = parse_color(&name)?;
+            }
+        }
+
+        Ok(theme)
+    }
+
+    pub fn color(&self, status: LetterStatus) -> Color {
+        match status {
+            LetterStatus::Correct => self.correct,
+            LetterStatus::Present => self.present,
+            LetterStatus::Absent => self.absent,
+            LetterStatus::Unknown => self.unknown,
+        }
+    }
+
+    /// How a letter is written on its square or key. Without colour, a correct letter goes in
+    /// square brackets, a present one in round brackets and an absent one in lower case, which
+    /// takes three columns.
+    pub fn label(&self, letter: char, status: LetterStatus) -> String {
+        if !self.monochrome {
+            return letter.to_string();
+        }
+
+        let letter = letter.to_ascii_uppercase();
+        match status {
+            LetterStatus::Correct => format!("[{}]", letter),
+            LetterStatus::Present => format!("({})", letter),
+            LetterStatus::Absent => format!(" {} ", letter.to_ascii_lowercase()),
+            LetterStatus::Unknown => format!(" {} ", letter),
+        }
+    }
+
+    /// A square or key with this status, letter and all
+    pub fn style(&self, status: LetterStatus) -> Style {
+        let style = Style::default().bg(self.color(status));
+
+        match status {
+            LetterStatus::Unknown => style,
+            _ => style.fg(self.text),
+        }
+    }
+}
+
+/// A theme as it's written in a file, with every colour optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    correct: Option<String>,
+    present: Option<String>,
+    absent: Option<String>,
+    unknown: Option<String>,
+    text: Option<String>,
+}
+
+/// A colour by name, like "light-green" or "dark grey", or in hex like "#6aaa64". "reset" is
+/// whatever the terminal's own colour is.
+fn parse_color(name: &str) -> Result<Color> {
+    if let Some(hex) = name.strip_prefix('#') {
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+        };
+
+        return match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(red), Some(green), Some(blue)) => Ok(Color::Rgb(red, green, blue)),
+            _ => Err(eyre!(
+                "\"{}\" isn't a colour, hex colours look like #6aaa64",
+                name
+            )),
+        };
+    }
+
+    let normalized: String = name
+        .chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .collect::<String>()
+        .to_lowercase()
+        .replace("grey", "gray");
+
+    Ok(match normalized.as_str() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" => Color::Gray,
+        "darkgray" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return Err(eyre!("\"{}\" isn't a colour Rustle knows", name)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_files_only_need_the_colours_they_change() {
+        let theme = Theme::parse("correct = \"#6aaa64\"\nabsent = \"Dark Grey\"\n").unwrap();
+
+        assert_eq!(
+            theme,
+            Theme {
+                correct: Color::Rgb(0x6a, 0xaa, 0x64),
+                absent: Color::DarkGray,
+                ..Theme::default()
+            }
+        );
+    }
+
+    #[test]
+    fn theme_files_are_checked() {
+        assert!(Theme::parse("correct = \"#6aaa6\"").is_err());
+        assert!(Theme::parse("correct = \"chartreuse\"").is_err());
+        assert!(Theme::parse("corect = \"green\"").is_err());
+    }
+
+    #[test]
+    fn monochrome_letters_are_marked_instead() {
+        let theme = Theme::monochrome();
+
+        assert_eq!(theme.label('a', LetterStatus::Correct), "[A]");
+        assert_eq!(theme.label('a', LetterStatus::Present), "(A)");
+        assert_eq!(theme.label('A', LetterStatus::Absent), " a ");
+        assert_eq!(Theme::default().label('a', LetterStatus::Correct), "a");
+    }
+
+    #[test]
+    fn empty_squares_keep_the_terminal_colours() {
+        let theme = Theme::high_contrast();
+
+        assert_eq!(
+            theme.style(LetterStatus::Unknown),
+            Style::default().bg(Color::Reset)
+        );
+        assert_eq!(
+            theme.style(LetterStatus::Absent),
+            Style::default().bg(Color::Gray).fg(Color::Black)
+        );
+    }
+}
@@ -1,74 +1,279 @@
 use tui::backend::Backend;
+use tui::buffer::Buffer;
 use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
-use tui::style::{Color, Style};
-use tui::widgets::{Block, Borders, Clear, Paragraph};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap};
 use tui::Frame;
 
-use super::state::GameStatus;
-use crate::app::App;
+use super::history::HistoryViewer;
+use super::hot_seat::HotSeat;
+use super::solver::Hints;
+use super::state::{AppState, Board, GameStatus, LetterStatus};
+use super::theme::Theme;
+use super::versus::{Connection, Role, Versus};
+use crate::app::{App, EndScreen};
+
+/// The letters on each row of the on-screen keyboard
+const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// The smallest terminal the game is drawn in full, with squares and keys 3 lines high
+const FULL_WIDTH: u16 = 52;
+const FULL_HEIGHT: u16 = 32;
+
+/// The smallest terminal the game can be played in at all. Anything smaller gets asked to be
+/// made bigger.
+const MIN_WIDTH: u16 = 36;
+const MIN_HEIGHT: u16 = 12;
+
+/// How tightly the game is packed in, which depends on the size of the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Density {
+    /// Squares and keys with borders around them
+    Full,
+    /// Squares and keys a single line high, with no borders
+    Compact,
+}
+
+impl Density {
+    /// `None` when the terminal is too small for even the compact layout. Without colour the game
+    /// is always compact, since marking letters with brackets takes the room borders would.
+    fn for_size(size: Rect, monochrome: bool) -> Option<Density> {
+        if size.width >= FULL_WIDTH && size.height >= FULL_HEIGHT && !monochrome {
+            Some(Density::Full)
+        } else if size.width >= MIN_WIDTH && size.height >= MIN_HEIGHT {
+            Some(Density::Compact)
+        } else {
+            None
+        }
+    }
+
+    fn square_height(self) -> u16 {
+        match self {
+            Density::Full => 3,
+            Density::Compact => 1,
+        }
+    }
+}
 
 pub fn draw<B>(frame: &mut Frame<B>, app: &App)
+where
+    B: Backend,
+{
+    draw_screen(frame, app);
+
+    if app.theme().monochrome {
+        frame.render_widget(Colorless, frame.size());
+    }
+}
+
+fn draw_screen<B>(frame: &mut Frame<B>, app: &App)
 where
     B: Backend,
 {
     let size = frame.size();
-    check_size(&size);
+    let density = match Density::for_size(size, app.theme().monochrome) {
+        Some(density) => density,
+        None => {
+            draw_too_small(frame, size);
+            return;
+        }
+    };
+
+    if let Some(viewer) = app.history() {
+        draw_history(frame, app, viewer, size, density);
+        return;
+    }
 
-    // Guess Area & Keyboard
+    // Guess Area & Keyboard. The compact board takes whatever the keyboard leaves.
+    let constraints = match density {
+        Density::Full => [
+            Constraint::Length(1),
+            Constraint::Length(19),
+            Constraint::Length(3),
+            Constraint::Min(1),
+        ],
+        Density::Compact => [
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ],
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints(
-            [
-                Constraint::Length(1),
-                Constraint::Length(19),
-                Constraint::Length(3),
-                Constraint::Min(1),
-            ]
-            .as_ref(),
-        )
+        .constraints(constraints.as_ref())
         .split(size);
 
     // Title
-    let title = draw_title();
+    let title = draw_title(app);
     frame.render_widget(title, chunks[0]);
 
-    draw_squares(frame, app, chunks[1]);
+    let mut board_area = chunks[1];
+
+    // The hint panel takes a strip on the right of the board, when it's turned on and there's
+    // room for it next to the board
+    let panel_width = app.state.word_length as u16 + 10;
+    let board_width = 3 * app.state.word_length as u16;
+    let hints = app
+        .hints()
+        .filter(|_| board_area.width >= board_width + panel_width);
+    if let Some(hints) = hints {
+        let board_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(panel_width)].as_ref())
+            .split(board_area);
+
+        frame.render_widget(draw_hints(app, hints), board_chunks[1]);
+        board_area = board_chunks[0];
+    }
+
+    // In a head-to-head game the opponent's board goes next to the player's
+    if let Some(versus) = app.versus() {
+        let board_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
+            .split(board_area);
+
+        draw_opponent(
+            frame,
+            &app.state,
+            app.theme(),
+            versus,
+            board_chunks[1],
+            density,
+        );
+        board_area = board_chunks[0];
+    }
 
-    let input = Paragraph::new(app.state.input.as_ref())
+    draw_squares(frame, &app.state, app.theme(), board_area, density);
+
+    // A hot seat word is hidden from the player who's about to guess it
+    let choosing = app.hot_seat().filter(|hot_seat| hot_seat.choosing);
+    let (label, input) = match choosing {
+        Some(_) => ("Secret Word", "*".repeat(app.state.input.len())),
+        None => ("Input", app.state.input.clone()),
+    };
+
+    // Rejected guesses explain themselves in the title of the input box
+    let input_title = match &app.state.message {
+        Some(message) => format!("{} - {}", label, message),
+        None => String::from(label),
+    };
+    let input = Paragraph::new(input)
         .style(Style::default())
-        .block(Block::default().borders(Borders::ALL).title("Input"));
+        .block(Block::default().borders(Borders::ALL).title(input_title));
     frame.render_widget(input, chunks[2]);
 
-    draw_keyboard(frame, app, chunks[3]);
+    match density {
+        Density::Full => draw_keyboard(frame, app, chunks[3]),
+        Density::Compact => frame.render_widget(draw_compact_keyboard(app), chunks[3]),
+    }
+
+    if let Some(hot_seat) = choosing {
+        frame.render_widget(Clear, chunks[1]);
+        frame.render_widget(draw_choosing(hot_seat), chunks[1]);
+
+        return;
+    }
+
+    // The stats and analysis screens cover everything but the title
+    let screen_area = chunks[1].union(chunks[3]);
+    match app.end_screen() {
+        EndScreen::Stats => {
+            frame.render_widget(Clear, screen_area);
+            frame.render_widget(draw_stats(app, screen_area), screen_area);
+
+            return;
+        }
+        EndScreen::Analysis => {
+            frame.render_widget(Clear, screen_area);
+            frame.render_widget(draw_analysis(app), screen_area);
+
+            return;
+        }
+        EndScreen::Result => {}
+    }
 
     // We want the popup to go over the input and keyboard
     let popup_area = chunks[2].union(chunks[3]);
 
-    match app.state.game_status {
-        GameStatus::Win => {
-            let paragraph = create_paragraph(format!(
-                "You have won! It took {} attempts.\nPress ESC or CTRL+C to exit",
-                app.state.attempt
-            ));
-            frame.render_widget(Clear, popup_area); //this clears out the background
-            frame.render_widget(paragraph, popup_area);
-        }
-        GameStatus::Lose => {
-            let paragraph = create_paragraph(format!(
-                "You lost. The correct word was \"{}\".\nPress ESC or CTRL+C to exit",
-                app.state.solution
-            ));
-            frame.render_widget(Clear, popup_area); //this clears out the background
+    if let Some(versus) = app.versus() {
+        let notice = match (versus.connection, versus.role, &versus.mismatch) {
+            (Connection::Mismatched, _, Some(mismatch)) => Some(mismatch.clone()),
+            (Connection::Waiting, Role::Host, _) => Some(format!(
+                "Waiting for an opponent to join on {}",
+                versus.address
+            )),
+            (Connection::Waiting, Role::Guest, _) => {
+                Some(String::from("Waiting for the host to start"))
+            }
+            _ => None,
+        };
+        if let Some(notice) = notice {
+            let paragraph = create_paragraph(format!("{}\nPress ESC or CTRL+C to exit", notice));
+            frame.render_widget(Clear, popup_area);
             frame.render_widget(paragraph, popup_area);
+
+            return;
         }
-        _ => {}
+    }
+
+    if let Some(saved) = app.resume_offer() {
+        let game = match saved.puzzle_number {
+            Some(puzzle) => format!("Rustle #{}", puzzle),
+            None => String::from("a game"),
+        };
+        let paragraph = create_paragraph(format!(
+            "You left {} unfinished after {} guesses.\nPress Y or Enter to carry on\nPress N for a new game",
+            game,
+            saved.guesses.len()
+        ));
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(paragraph, popup_area);
+
+        return;
+    }
+
+    if let Some(result) = app.result() {
+        let paragraph = create_paragraph(format!("{}\n{}", result, end_of_game_keys(app)));
+        frame.render_widget(Clear, popup_area); //this clears out the background
+        frame.render_widget(paragraph, popup_area);
+    }
+}
+
+/// What can be done from the win or lose popup
+fn end_of_game_keys(app: &App) -> &'static str {
+    if app.hot_seat().is_some() {
+        "Press N for the next round, Enter for analysis\nPress ESC or CTRL+C to exit"
+    } else if app.shows_stats() {
+        "Press Enter for statistics\nPress ESC or CTRL+C to exit"
+    } else {
+        "Press Enter for analysis\nPress ESC or CTRL+C to exit"
     }
 }
 
+/// The screen before a hot seat round, while one player types the word for the other
+fn draw_choosing<'a>(hot_seat: &HotSeat) -> Paragraph<'a> {
+    let setter = hot_seat.setter.name();
+    let guesser = hot_seat.guesser().name();
+
+    Paragraph::new(format!(
+        "\nRound {}\n\n{}, type a word for {} to guess.\n{}, no peeking!\n\nPress Enter once it's typed in",
+        hot_seat.round, setter, guesser, guesser
+    ))
+    .block(Block::default().borders(Borders::ALL).title("Hot Seat"))
+    .alignment(Alignment::Center)
+}
+
 fn draw_keyboard<B>(frame: &mut Frame<B>, app: &App, area: Rect)
 where
     B: Backend,
 {
+    // With several boards each key is split to show every board's colour, which needs a little
+    // more room
+    let key_width: u16 = if app.state.boards.len() > 1 { 4 } else { 3 };
+
     // row chunks are 3 rows with a length ("height") of 3 lines
     let row_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -84,243 +289,692 @@ where
         )
         .split(area);
 
-    // Top row ("qwerty") has 10 keys, so we create 10 columns with a length of 3 and add in some
-    // padding
-    let top_row_letters = ["Q", "W", "E", "R", "T", "Y", "U", "I", "O", "P"];
-    let top_row_horizontal_padding = (area.width - 3 * 10) / 2;
-    let top_row_col_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(
-            [
-                Constraint::Length(top_row_horizontal_padding),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(top_row_horizontal_padding),
-            ]
-            .as_ref(),
-        )
-        .split(row_chunks[0]);
-
-    // Home row ("asdfg") has 9 keys, so we create 9 columns with a length of 3 and add in some
-    // padding
-    let home_row_letters = ["A", "S", "D", "F", "G", "H", "J", "K", "L"];
-    let home_row_horizontal_padding = (area.width - 3 * 9) / 2;
-    let home_row_col_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(
-            [
-                Constraint::Length(home_row_horizontal_padding),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(home_row_horizontal_padding),
-            ]
-            .as_ref(),
-        )
-        .split(row_chunks[1]);
-
-    // Bottom row has 7 keys, so we create 7 columns with a length of 3 and add in some padding as
-    // per usual.
-    let bottom_row_letters = ["Z", "X", "C", "V", "B", "N", "M"];
-    let bottom_row_horizontal_padding = (area.width - 3 * 7) / 2;
-    let bottom_row_col_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(
-            [
-                Constraint::Length(bottom_row_horizontal_padding),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(bottom_row_horizontal_padding),
-            ]
-            .as_ref(),
-        )
-        .split(row_chunks[2]);
-
-    for i in 1..11 {
-        let key_color = app
-            .state
-            .keyboard_colors
-            .get(
-                &top_row_letters[i - 1]
-                    .chars()
-                    .next()
-                    .unwrap()
-                    .to_ascii_lowercase(),
-            )
-            .unwrap();
-
-        let keys = Paragraph::new(top_row_letters[i - 1])
-            .style(Style::default().bg(*key_color))
-            .block(Block::default().borders(Borders::ALL))
-            .alignment(Alignment::Center);
+    for (letters, row_chunk) in KEYBOARD_ROWS.iter().zip(row_chunks.iter()) {
+        // Each row gets a column with a length of `key_width` for every key, plus some padding
+        let horizontal_padding = area.width.saturating_sub(key_width * letters.len() as u16) / 2;
+        let mut col_constraints = vec![Constraint::Length(horizontal_padding)];
+        col_constraints.extend(std::iter::repeat_n(
+            Constraint::Length(key_width),
+            letters.len(),
+        ));
+        col_constraints.push(Constraint::Length(horizontal_padding));
+
+        let col_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(col_constraints.as_slice())
+            .split(*row_chunk);
+
+        for (letter, key_chunk) in letters.chars().zip(col_chunks.iter().skip(1)) {
+            let key = if let [board] = app.state.boards.as_slice() {
+                Paragraph::new(letter.to_ascii_uppercase().to_string())
+                    .style(app.theme().style(board.key_statuses[&letter]))
+                    .block(Block::default().borders(Borders::ALL))
+                    .alignment(Alignment::Center)
+            } else {
+                split_key(app, letter, key_width)
+            };
 
-        frame.render_widget(keys, top_row_col_chunks[i]);
+            frame.render_widget(key, *key_chunk);
+        }
     }
+}
 
-    for i in 1..10 {
-        let key_color = app
-            .state
-            .keyboard_colors
-            .get(
-                &home_row_letters[i - 1]
-                    .chars()
-                    .next()
-                    .unwrap()
-                    .to_ascii_lowercase(),
-            )
-            .unwrap();
+/// The keyboard as a line of letters per row, each letter on its key's colour. With several
+/// boards a key takes its colour from the first board that's still being played, which is the one
+/// the hints are for.
+fn draw_compact_keyboard<'a>(app: &App) -> Paragraph<'a> {
+    let boards = &app.state.boards;
+    let board = boards
+        .iter()
+        .find(|board| !board.is_solved())
+        .unwrap_or(&boards[0]);
+
+    let rows: Vec<Spans> = KEYBOARD_ROWS
+        .iter()
+        .map(|letters| {
+            let keys: Vec<Span> = letters
+                .chars()
+                .map(|letter| {
+                    let status = board.key_statuses[&letter];
+                    let label = app.theme().label(letter.to_ascii_uppercase(), status);
+
+                    Span::styled(format!("{:^3}", label), app.theme().style(status))
+                })
+                .collect();
+
+            Spans::from(keys)
+        })
+        .collect();
+
+    Paragraph::new(rows).alignment(Alignment::Center)
+}
 
-        let keys = Paragraph::new(home_row_letters[i - 1])
-            .style(Style::default().bg(*key_color))
-            .block(Block::default().borders(Borders::ALL))
-            .alignment(Alignment::Center);
+/// A key with a strip of colour for each board along its top and bottom edges. The first half of
+/// the boards go along the top and the rest along the bottom, in the same order as on screen.
+fn split_key<'a>(app: &App, letter: char, width: u16) -> Paragraph<'a> {
+    let boards = &app.state.boards;
+    let (top, bottom) = boards.split_at(boards.len().div_ceil(2));
+
+    let strip = |boards: &[Board]| -> Spans<'a> {
+        let cells: Vec<Span> = (0..usize::from(width))
+            .map(|x| {
+                let board = &boards[x * boards.len() / usize::from(width)];
+                let color = app.theme().color(board.key_statuses[&letter]);
+
+                Span::styled(" ", Style::default().bg(color))
+            })
+            .collect();
+
+        Spans::from(cells)
+    };
+
+    Paragraph::new(vec![
+        strip(top),
+        Spans::from(letter.to_ascii_uppercase().to_string()),
+        strip(bottom),
+    ])
+    .alignment(Alignment::Center)
+}
+
+/// One board's worth of guesses, ready to be drawn as a grid of squares
+struct Grid<'a> {
+    guesses: &'a [String],
+    square_statuses: &'a [Vec<LetterStatus>],
+    /// Rows on the board, counting the ones that haven't been guessed yet
+    rows: usize,
+    word_length: usize,
+    theme: &'a Theme,
+}
 
-        frame.render_widget(keys, home_row_col_chunks[i]);
+impl<'a> Grid<'a> {
+    fn for_board(state: &'a AppState, board: &'a Board, theme: &'a Theme) -> Grid<'a> {
+        Grid {
+            // Solved boards stop taking guesses, so they only get the rows they had by then
+            guesses: &state.guesses[..board.square_statuses.len()],
+            square_statuses: &board.square_statuses,
+            rows: match state.max_attempts {
+                Some(max_attempts) => max_attempts,
+                None => state.guesses.len() + 1,
+            },
+            word_length: state.word_length,
+            theme,
+        }
     }
+}
 
-    for i in 1..8 {
-        let key_color = app
-            .state
-            .keyboard_colors
-            .get(
-                &bottom_row_letters[i - 1]
-                    .chars()
-                    .next()
-                    .unwrap()
-                    .to_ascii_lowercase(),
-            )
-            .unwrap();
+fn draw_squares<B>(
+    frame: &mut Frame<B>,
+    state: &AppState,
+    theme: &Theme,
+    area: Rect,
+    density: Density,
+) where
+    B: Backend,
+{
+    if let [board] = state.boards.as_slice() {
+        draw_grid(
+            frame,
+            &Grid::for_board(state, board, theme),
+            area,
+            density.square_height(),
+        );
+        return;
+    }
 
-        let keys = Paragraph::new(bottom_row_letters[i - 1])
-            .style(Style::default().bg(*key_color))
-            .block(Block::default().borders(Borders::ALL))
-            .alignment(Alignment::Center);
+    // Several boards are tiled in as many columns as fit, with squares a single line high to
+    // leave room for more rows
+    let gap = 2;
+    let board_width = 3 * state.word_length as u16;
+    let columns =
+        usize::from((area.width + gap) / (board_width + gap)).clamp(1, state.boards.len());
+    let grid_rows = state.boards.len().div_ceil(columns);
+    // Spread the boards evenly, so four boards make a square rather than three and one
+    let columns = state.boards.len().div_ceil(grid_rows);
 
-        frame.render_widget(keys, bottom_row_col_chunks[i]);
+    let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, grid_rows as u32); grid_rows].as_slice())
+        .split(area);
+
+    for (boards, row_chunk) in state.boards.chunks(columns).zip(row_chunks.iter()) {
+        let used_width = boards.len() as u16 * (board_width + gap) - gap;
+        let horizontal_padding = area.width.saturating_sub(used_width) / 2;
+
+        let mut col_constraints = vec![Constraint::Length(horizontal_padding)];
+        for i in 0..boards.len() {
+            if i > 0 {
+                col_constraints.push(Constraint::Length(gap));
+            }
+            col_constraints.push(Constraint::Length(board_width));
+        }
+        col_constraints.push(Constraint::Min(0));
+
+        let col_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(col_constraints.as_slice())
+            .split(*row_chunk);
+
+        // Boards sit in every other column, after the padding
+        for (board, board_chunk) in boards.iter().zip(col_chunks.iter().skip(1).step_by(2)) {
+            draw_grid(
+                frame,
+                &Grid::for_board(state, board, theme),
+                *board_chunk,
+                1,
+            );
+        }
     }
 }
 
-fn draw_squares<B>(frame: &mut Frame<B>, app: &App, area: Rect)
-where
+/// The opponent's board has the colours of their guesses but not the letters
+fn draw_opponent<B>(
+    frame: &mut Frame<B>,
+    state: &AppState,
+    theme: &Theme,
+    versus: &Versus,
+    area: Rect,
+    density: Density,
+) where
     B: Backend,
 {
-    let horizontal_padding = (area.width - 15) / 2;
-    let guesses = &app.state.guesses;
-    let square_colors = &app.state.square_colors;
+    // Without colour the hidden letters need something to put brackets around
+    let hidden = if theme.monochrome { "*" } else { " " };
+    let blanks = vec![hidden.repeat(state.word_length); versus.opponent_rows.len()];
+    let grid = Grid {
+        guesses: &blanks,
+        square_statuses: &versus.opponent_rows,
+        rows: match state.max_attempts {
+            Some(max_attempts) => max_attempts,
+            None => versus.opponent_rows.len() + 1,
+        },
+        word_length: state.word_length,
+        theme,
+    };
+
+    draw_grid(frame, &grid, area, density.square_height());
+}
 
-    // row chunks are 6 rows with a length ("height") of 3 lines
+/// Draws a board as rows of squares, `square_height` lines high. Squares 3 lines high get a
+/// border, and squares a single line high are just the letter on its colour.
+fn draw_grid<B>(frame: &mut Frame<B>, grid: &Grid, area: Rect, square_height: u16)
+where
+    B: Backend,
+{
+    let word_length = grid.word_length;
+    let horizontal_padding = area.width.saturating_sub(3 * word_length as u16) / 2;
+    let guesses = grid.guesses;
+    let square_statuses = grid.square_statuses;
+
+    // Only as many rows as fit are shown. When there are more attempts than that, the board
+    // scrolls so the row being typed into stays visible.
+    let visible_rows = usize::from(area.height / square_height);
+    let current_row = guesses.len().min(grid.rows.saturating_sub(1));
+    let first_row = (current_row + 1).saturating_sub(visible_rows);
+    let shown_rows = grid.rows.min(visible_rows);
+
+    // row chunks have a length ("height") of `square_height` lines each
+    let mut row_constraints = vec![Constraint::Length(square_height); shown_rows];
+    // Padding for the bottom
+    row_constraints.push(Constraint::Min(0));
     let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(row_constraints.as_slice())
+        .split(area);
+
+    // column chunks are a column with a length ("width") of 3 for each letter, with padding on
+    // either side
+    let mut col_constraints = vec![Constraint::Length(horizontal_padding)];
+    col_constraints.extend(std::iter::repeat_n(Constraint::Length(3), word_length));
+    col_constraints.push(Constraint::Length(horizontal_padding));
+
+    // We iterate through all of the constraints except the padding
+    for (i, row_chunk) in row_chunks.iter().take(shown_rows).enumerate() {
+        let row = first_row + i;
+        let col_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(col_constraints.as_slice())
+            .split(*row_chunk);
+
+        // We want a word broken into its individual letters for each row. Rows that haven't been
+        // guessed yet are left blank.
+        let mut letters = guesses.get(row).map(|guess| guess.chars());
+        let statuses = square_statuses.get(row);
+
+        for (n, square_chunk) in col_chunks.iter().skip(1).take(word_length).enumerate() {
+            let (text, status) = match letters.as_mut().and_then(|letters| letters.next()) {
+                Some(letter) => {
+                    let status = statuses
+                        .and_then(|statuses| statuses.get(n))
+                        .copied()
+                        .unwrap_or(LetterStatus::Unknown);
+
+                    (grid.theme.label(letter, status), status)
+                }
+                None if square_height == 1 => (String::from("·"), LetterStatus::Unknown),
+                None => (String::new(), LetterStatus::Unknown),
+            };
+
+            let mut colored_square = Paragraph::new(text)
+                .style(grid.theme.style(status))
+                .alignment(Alignment::Center);
+            if square_height > 1 {
+                colored_square = colored_square.block(Block::default().borders(Borders::ALL));
+            }
+
+            frame.render_widget(colored_square, *square_chunk);
+        }
+    }
+}
+
+fn draw_stats<'a>(app: &App, area: Rect) -> Paragraph<'a> {
+    let stats = app.stats();
+
+    // The bar for the game that just finished is highlighted, if it was a win
+    let highlighted = match app.state.game_status {
+        GameStatus::Win => Some(app.state.guesses.len()),
+        _ => None,
+    };
+
+    let mut lines = vec![
+        Spans::from(""),
+        Spans::from(format!(
+            "Played {} | Win % {} | Streak {} | Max Streak {}",
+            stats.played,
+            stats.win_percentage(),
+            stats.current_streak,
+            stats.max_streak
+        )),
+    ];
+    if !stats.speedrun_bests.is_empty() {
+        let bests: Vec<String> = stats
+            .speedrun_bests
+            .iter()
+            .map(|(seconds, best)| format!("{} in {}", best, format_duration(seconds * 1000)))
+            .collect();
+        lines.push(Spans::from(format!(
+            "Speedrun Bests | {}",
+            bests.join(" | ")
+        )));
+    }
+    lines.extend([
+        Spans::from(""),
+        Spans::from("Guess Distribution"),
+        Spans::from(""),
+    ]);
+
+    // Leave room for the borders, the attempt label and a little breathing space
+    let max_bar_width = u32::from(area.width.saturating_sub(10));
+    let max_count = stats.distribution.iter().copied().max().unwrap_or(0).max(1);
+
+    for (i, count) in stats.distribution.iter().enumerate() {
+        let attempts = i + 1;
+        let label = format!(" {} ", count);
+        let bar_width = (count * max_bar_width / max_count).max(label.len() as u32) as usize;
+        let color = if highlighted == Some(attempts) {
+            app.theme().correct
+        } else {
+            Color::Gray
+        };
+        let mut style = highlight(app, Style::default().bg(color).fg(Color::Black));
+        if highlighted == Some(attempts) {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+
+        lines.push(Spans::from(vec![
+            Span::raw(format!("{} ", attempts)),
+            Span::styled(format!("{:>width$}", label, width = bar_width), style),
+            // Pad every bar to the same width so centring the text keeps them lined up
+            Span::raw(" ".repeat((max_bar_width as usize).saturating_sub(bar_width))),
+        ]));
+    }
+
+    lines.push(Spans::from(""));
+    lines.push(Spans::from(
+        "Press Enter for analysis, ESC or CTRL+C to exit",
+    ));
+
+    Paragraph::new(lines)
+        .style(Style::default().bg(Color::White).fg(Color::Black))
+        .block(Block::default().borders(Borders::ALL).title("Statistics"))
+        .alignment(Alignment::Center)
+}
+
+/// A row per guess with how many words it left, the bits it got and was expected to get, and
+/// the solver's pick with the bits that was expected to get
+fn draw_analysis<'a>(app: &App) -> Paragraph<'a> {
+    let width = app.state.word_length;
+    let mut lines = vec![
+        Spans::from(""),
+        Spans::from(format!(
+            "{:<width$} {:>5} {:>5} {:>5} {:>5}  {:<width$} {:>5}",
+            "Guess",
+            "Words",
+            "Left",
+            "Got",
+            "Exp",
+            "Best",
+            "Exp",
+            width = width
+        )),
+        Spans::from(""),
+    ];
+
+    match app.analysis() {
+        Some(analysis) => {
+            for row in analysis {
+                // A guess that got less than the best pick expected is picked out, so it's easy
+                // to see where things went wrong
+                let style = if row.expected + 0.005 < row.best.entropy {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default()
+                };
+
+                lines.push(Spans::from(vec![
+                    Span::styled(
+                        format!(
+                            "{:<width$} {:>5} {:>5} {:>5.2} {:>5.2}",
+                            row.guess,
+                            row.before,
+                            row.after,
+                            row.bits,
+                            row.expected,
+                            width = width
+                        ),
+                        style,
+                    ),
+                    Span::raw(format!(
+                        "  {:<width$} {:>5.2}",
+                        row.best.word,
+                        row.best.entropy,
+                        width = width
+                    )),
+                ]));
+            }
+        }
+        None => lines.push(Spans::from("Analysing...")),
+    }
+
+    lines.push(Spans::from(""));
+    lines.push(Spans::from(
+        "Words: possible before the guess, Left: after it",
+    ));
+    lines.push(Spans::from(
+        "Got: bits the colours gave, Exp: bits expected",
+    ));
+    if app.state.boards.len() > 1 {
+        lines.push(Spans::from("Only the first board is analysed."));
+    }
+    lines.push(Spans::from(""));
+    lines.push(Spans::from("Press Enter to go back, ESC or CTRL+C to exit"));
+
+    Paragraph::new(lines)
+        .style(Style::default().bg(Color::White).fg(Color::Black))
+        .block(Block::default().borders(Borders::ALL).title("Analysis"))
+        .alignment(Alignment::Center)
+}
+
+/// The past games screen: a list of games, or the chosen one replayed on its own board
+fn draw_history<B>(
+    frame: &mut Frame<B>,
+    app: &App,
+    viewer: &HistoryViewer,
+    area: Rect,
+    density: Density,
+) where
+    B: Backend,
+{
+    let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                // Padding for the bottom
+                Constraint::Length(1),
                 Constraint::Min(1),
+                Constraint::Length(1),
             ]
             .as_ref(),
         )
         .split(area);
 
-    // We iterate through all of the constraints except the last
-    for i in 0..6 {
-        // column chunks are 5 rows with a length ("width") of 3 lines
-        let col_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(
-                [
-                    Constraint::Length(horizontal_padding),
-                    Constraint::Length(3),
-                    Constraint::Length(3),
-                    Constraint::Length(3),
-                    Constraint::Length(3),
-                    Constraint::Length(3),
-                    Constraint::Length(horizontal_padding),
-                ]
-                .as_ref(),
-            )
-            .split(row_chunks[i]);
-
-        // We want a word broken into its individual letters for each row
-        let mut letters = if let Some(guess) = guesses.get(i) {
-            guess.chars()
-        } else {
-            // For the rest of the vector that isn't present, we just give a
-            // blank Char with 5 spaces
-            "     ".chars()
-        };
+    let (entry, shown) = match (viewer.selected_entry(), viewer.replaying) {
+        (Some(entry), Some(shown)) => (entry, shown),
+        _ => {
+            frame.render_widget(title("Rustle History"), chunks[0]);
+            frame.render_widget(draw_history_list(app, viewer, chunks[1]), chunks[1]);
+            frame.render_widget(
+                title("Up/Down to pick a game, Enter to replay it, ESC to exit"),
+                chunks[2],
+            );
+            return;
+        }
+    };
 
-        let colors = if let Some(color_vec) = square_colors.get(i) {
-            color_vec.clone()
-        } else {
-            vec![
-                Color::Reset,
-                Color::Reset,
-                Color::Reset,
-                Color::Reset,
-                Color::Reset,
-            ]
-        };
+    let mut heading = format!("{} {}", entry.finished.format("%Y-%m-%d %H:%M"), entry.mode);
+    if let Some(puzzle) = entry.puzzle_number {
+        heading.push_str(&format!(" #{}", puzzle));
+    }
+    frame.render_widget(title(heading), chunks[0]);
+
+    draw_squares(
+        frame,
+        &entry.replay(shown, app.state.color_blind),
+        app.theme(),
+        chunks[1],
+        density,
+    );
+
+    let footer = if shown < entry.guesses.len() {
+        format!(
+            "Row {} of {} - Left/Right to step, Backspace for the list",
+            shown,
+            entry.guesses.len()
+        )
+    } else {
+        format!(
+            "{} in {} - Backspace for the list",
+            if entry.won { "Won" } else { "Lost" },
+            format_duration(entry.elapsed_ms)
+        )
+    };
+    frame.render_widget(title(footer), chunks[2]);
+}
 
-        for n in 1..6 {
-            let colored_square = if let Some(letter) = letters.next() {
-                Paragraph::new(letter.to_string())
-                    .style(Style::default().bg(colors[n - 1]))
-                    .block(Block::default().borders(Borders::ALL))
-                    .alignment(Alignment::Center)
+fn draw_history_list<'a>(app: &App, viewer: &HistoryViewer, area: Rect) -> Paragraph<'a> {
+    if viewer.entries.is_empty() {
+        return Paragraph::new("No games have been finished yet")
+            .block(Block::default().borders(Borders::ALL))
+            .alignment(Alignment::Center);
+    }
+
+    // The list scrolls to keep the selected game in view
+    let visible = usize::from(area.height.saturating_sub(2)).max(1);
+    let first = (viewer.selected + 1).saturating_sub(visible);
+
+    let lines: Vec<Spans> = viewer
+        .entries
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .map(|(i, entry)| {
+            let score = match (entry.won, entry.max_attempts) {
+                (true, Some(max_attempts)) => format!("{}/{}", entry.guesses.len(), max_attempts),
+                (true, None) => format!("{}/∞", entry.guesses.len()),
+                (false, Some(max_attempts)) => format!("X/{}", max_attempts),
+                (false, None) => String::from("X/∞"),
+            };
+            let line = format!(
+                "{} {:<12} {} {:>5} {:>6}",
+                entry.finished.format("%Y-%m-%d %H:%M"),
+                entry.mode,
+                entry.solutions().join(" "),
+                score,
+                format_duration(entry.elapsed_ms)
+            );
+
+            let style = if i == viewer.selected {
+                highlight(app, Style::default().bg(Color::LightCyan).fg(Color::Black))
             } else {
-                Paragraph::new("")
-                    .style(Style::default().bg(Color::Reset))
-                    .block(Block::default().borders(Borders::ALL))
-                    .alignment(Alignment::Center)
+                Style::default()
             };
+            Spans::from(Span::styled(line, style))
+        })
+        .collect();
 
-            frame.render_widget(colored_square, col_chunks[n]);
-        }
+    Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Games"))
+}
+
+/// Minutes and seconds, like 3:07
+fn format_duration(ms: u64) -> String {
+    let seconds = ms / 1000;
+
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// A line of centred text, like the game's title
+fn title<'a>(text: impl Into<String>) -> Paragraph<'a> {
+    Paragraph::new(text.into())
+        .style(Style::default().fg(Color::LightCyan))
+        .alignment(Alignment::Center)
+}
+
+/// Lists the solver's best next guesses with the bits each is expected to give. Guesses that
+/// could be the answer are coloured in. The hints for an earlier guess aren't shown while the
+/// solver is still on the latest one.
+fn draw_hints<'a>(app: &App, hints: &Hints) -> Paragraph<'a> {
+    let block = Block::default().borders(Borders::ALL).title("Hints");
+    if app.hints_pending() {
+        return Paragraph::new("Thinking...")
+            .block(block)
+            .alignment(Alignment::Center);
     }
+
+    let mut lines = vec![
+        Spans::from(match hints.candidates {
+            1 => String::from("1 word left"),
+            left => format!("{} left", left),
+        }),
+        Spans::from(""),
+    ];
+
+    for suggestion in &hints.suggestions {
+        let style = if suggestion.candidate {
+            Style::default()
+                .fg(app.theme().correct)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+
+        lines.push(Spans::from(vec![
+            Span::styled(suggestion.word.clone(), style),
+            Span::raw(format!(" {:>5.2}", suggestion.entropy)),
+        ]));
+    }
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
 }
 
-fn draw_title<'a>() -> Paragraph<'a> {
-    Paragraph::new("Rustle")
+fn draw_title<'a>(app: &App) -> Paragraph<'a> {
+    let mut title = match app.state.puzzle_number {
+        Some(puzzle) => format!("Rustle #{}", puzzle),
+        None => String::from("Rustle"),
+    };
+    if app.state.candidates.is_some() {
+        title.push_str(" Absurdle");
+    }
+    if app.state.hard_mode {
+        title.push_str(" (Hard Mode)");
+    }
+    if let Some(versus) = app.versus() {
+        title.push_str(&format!(" - Opponent: {}", versus.describe_opponent()));
+    }
+    if let Some(hot_seat) = app.hot_seat() {
+        title.push_str(&format!(" - {}", hot_seat.describe_score()));
+    }
+    if let Some(speedrun) = app.speedrun() {
+        title.push_str(&format!(" - {} solved", speedrun.solved));
+    }
+    if let Some(survival) = app.survival() {
+        // The pool only pays for a word once it's over, so the guesses on the board come off it
+        // here
+        let left = match app.state.game_status {
+            GameStatus::InProgress => survival.pool.saturating_sub(app.state.guesses.len()),
+            _ => survival.pool,
+        };
+        match left {
+            1 => title.push_str(&format!(" - {} solved - 1 guess left", survival.solved)),
+            left => title.push_str(&format!(
+                " - {} solved - {} guesses left",
+                survival.solved, left
+            )),
+        }
+    }
+    if let Some(left) = app.time_left() {
+        // Rounded up, so the clock only shows 0:00 once the time is up
+        let ms = left.as_millis() as u64;
+        title.push_str(&format!(" - {} left", format_duration(ms + 999)));
+    }
+    if let Some(candidates) = &app.state.candidates {
+        match candidates.len() {
+            1 => title.push_str(" - 1 word left"),
+            left => title.push_str(&format!(" - {} words left", left)),
+        }
+    }
+
+    Paragraph::new(title)
         .style(Style::default().fg(Color::LightCyan))
         .alignment(Alignment::Center)
 }
 
-fn check_size(rect: &Rect) {
-    if rect.width < 52 {
-        panic!("Require width >= 52, (got {})", rect.width);
+/// A style that stands out in colour, or in reverse video when colour is left out
+fn highlight(app: &App, colored: Style) -> Style {
+    if app.theme().monochrome {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        colored
     }
-    if rect.height < 28 {
-        panic!("Require height >= 28, (got {})", rect.height);
+}
+
+/// Takes the colour out of everything drawn under it, leaving bold and reversed text as they are
+struct Colorless;
+
+impl Widget for Colorless {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            }
+        }
     }
 }
 
+/// Shown in place of the game while the terminal is too small to play in
+fn draw_too_small<B>(frame: &mut Frame<B>, area: Rect)
+where
+    B: Backend,
+{
+    let text = format!(
+        "Please enlarge your window\n\nRustle needs at least {}x{}, this is {}x{}",
+        MIN_WIDTH, MIN_HEIGHT, area.width, area.height
+    );
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(Color::LightCyan))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
+
+/// A popup's text, wrapped to fit on narrow terminals
 fn create_paragraph(text: String) -> Paragraph<'static> {
     Paragraph::new(text)
         .style(Style::default().bg(Color::White).fg(Color::Black))
         .block(Block::default().borders(Borders::ALL))
         .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
 }
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use super::state::LetterStatus;
+
+/// Which end of a head-to-head game this is. The host picks the word and the guest plays it too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Host,
+    Guest,
+}
+
+/// How a player's game is going, as far as their opponent is told
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerStatus {
+    Playing,
+    Won,
+    Lost,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connection {
+    /// The host is waiting for someone to join, or the guest for the word
+    Waiting,
+    Playing,
+    /// The opponent quit or the connection dropped. The game can still be finished alone.
+    Left,
+    /// The host's word can't be played with the guest's word length or word list, so there's no
+    /// game to play
+    Mismatched,
+}
+
+/// A head-to-head game against someone on the other end of a TCP connection
+#[derive(Debug, Clone)]
+pub struct Versus {
+    pub role: Role,
+    /// Where the host is listening
+    pub address: String,
+    pub connection: Connection,
+    /// The colours of the opponent's guesses, without the letters
+    pub opponent_rows: Vec<Vec<LetterStatus>>,
+    pub opponent_status: PlayerStatus,
+    /// Why the host's word can't be played, when it can't
+    pub mismatch: Option<String>,
+}
+
+impl Versus {
+    pub fn new(role: Role, address: String) -> Versus {
+        Versus {
+            role,
+            address,
+            connection: Connection::Waiting,
+            opponent_rows: Vec::new(),
+            opponent_status: PlayerStatus::Playing,
+            mismatch: None,
+        }
+    }
+
+    /// A few words about the opponent for the title bar
+    pub fn describe_opponent(&self) -> String {
+        let guesses = self.opponent_rows.len();
+
+        match (self.connection, self.opponent_status) {
+            (Connection::Waiting, _) => String::from("waiting"),
+            (Connection::Mismatched, _) => String::from("can't play"),
+            (_, PlayerStatus::Won) => format!("won in {}", guesses),
+            (_, PlayerStatus::Lost) => String::from("lost"),
+            (Connection::Left, _) => String::from("left"),
+            (Connection::Playing, PlayerStatus::Playing) if guesses == 1 => String::from("1 guess"),
+            (Connection::Playing, PlayerStatus::Playing) => format!("{} guesses", guesses),
+        }
+    }
+}
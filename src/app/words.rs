@@ -1,7 +1,21 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use eyre::{Result, WrapErr};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 
-const VALID_WORDS: [&str; 2315] = [
+/// The length of a word in the original game, and the only length the built-in list has
+pub const DEFAULT_WORD_LENGTH: usize = 5;
+/// The shortest words a game can be played with
+pub const MIN_WORD_LENGTH: usize = 4;
+/// The longest words a game can be played with, which still fit on the smallest terminal
+pub const MAX_WORD_LENGTH: usize = 11;
+
+static VALID_WORDS: [&str; 2315] = [
     "aback", "abase", "abate", "abbey", "abbot", "abhor", "abide", "abled", "abode", "abort",
     "about", "above", "abuse", "abyss", "acorn", "acrid", "actor", "acute", "adage", "adapt",
     "adept", "admin", "admit", "adobe", "adopt", "adore", "adorn", "adult", "affix", "afire",
@@ -236,12 +250,341 @@ const VALID_WORDS: [&str; 2315] = [
     "young", "youth", "zebra", "zesty", "zonal",
 ];
 
-pub fn random_word() -> String {
-    let mut rng = thread_rng();
+/// The words a game is played with: the answers a solution is picked from, and every word that
+/// is accepted as a guess
+#[derive(Debug, Clone)]
+pub struct WordList {
+    answers: Vec<String>,
+    allowed: HashSet<String>,
+}
+
+impl WordList {
+    /// Answers are always allowed as guesses, even if the allowed list leaves them out
+    pub fn new(answers: Vec<String>, allowed: Vec<String>) -> WordList {
+        let allowed = allowed.into_iter().chain(answers.iter().cloned()).collect();
+
+        WordList { answers, allowed }
+    }
+
+    /// The list Rustle ships with, used for both answers and guesses
+    pub fn builtin() -> Arc<WordList> {
+        static BUILTIN: OnceLock<Arc<WordList>> = OnceLock::new();
+
+        BUILTIN
+            .get_or_init(|| {
+                let words: Vec<String> = VALID_WORDS.iter().map(|word| word.to_string()).collect();
+
+                Arc::new(WordList::new(words.clone(), words))
+            })
+            .clone()
+    }
+
+    /// Loads the lists from plain text files with one word per line, keeping only the words
+    /// that are `length` letters long. Either list that isn't given falls back to the built-in
+    /// one.
+    pub fn load(answers: Option<&Path>, allowed: Option<&Path>, length: usize) -> Result<WordList> {
+        let builtin = || VALID_WORDS.iter().map(|word| word.to_string()).collect();
+        let of_length = |words: Vec<String>| -> Vec<String> {
+            words
+                .into_iter()
+                .filter(|word| word.len() == length)
+                .collect()
+        };
+
+        let answers = of_length(match answers {
+            Some(path) => read_words(path)?,
+            None => builtin(),
+        });
+        let allowed = of_length(match allowed {
+            Some(path) => read_words(path)?,
+            None => builtin(),
+        });
+
+        if answers.is_empty() {
+            return Err(eyre::eyre!(
+                "the answer list doesn't have any {} letter words in it",
+                length
+            ));
+        }
+
+        Ok(WordList::new(answers, allowed))
+    }
+
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    /// Every accepted guess, answers included, in no particular order
+    pub fn allowed(&self) -> impl Iterator<Item = &String> {
+        self.allowed.iter()
+    }
+
+    pub fn random_word(&self) -> String {
+        let mut rng = thread_rng();
 
-    VALID_WORDS.choose(&mut rng).unwrap().to_string()
+        self.answers.choose(&mut rng).unwrap().to_string()
+    }
+
+    /// Picks `count` different answers, or repeats some if there aren't enough to go around
+    pub fn random_words(&self, count: usize) -> Vec<String> {
+        let mut rng = thread_rng();
+        let mut words: Vec<String> = self
+            .answers
+            .choose_multiple(&mut rng, count)
+            .cloned()
+            .collect();
+
+        while words.len() < count {
+            words.push(self.random_word());
+        }
+
+        words
+    }
+
+    /// The solution for a daily puzzle. Stepping through the answers by a stride that shares no
+    /// factor with their count visits every word once before repeating, without going in
+    /// alphabetical order.
+    pub fn daily_word(&self, puzzle: u32) -> String {
+        let len = self.answers.len();
+        let stride = (1637..).find(|stride| gcd(*stride, len) == 1).unwrap();
+        let index = (puzzle as usize % len * stride) % len;
+
+        self.answers[index].to_string()
+    }
+
+    pub fn check_validity(&self, word: &str) -> bool {
+        self.allowed.contains(word)
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
 }
 
-pub fn check_validity(word: &str) -> bool {
-    VALID_WORDS.contains(&word)
+/// What's wrong with a line in a word list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Words have to be between `MIN_WORD_LENGTH` and `MAX_WORD_LENGTH` letters long
+    WrongLength(usize),
+    NotLetters,
+    /// The same word was already on the given line
+    Duplicate(usize),
+}
+
+/// A line in a word list that couldn't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedEntry {
+    /// Line numbers start at 1, like in an editor
+    pub line: usize,
+    pub entry: String,
+    pub problem: Problem,
+}
+
+impl Display for MalformedEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.problem {
+            Problem::WrongLength(len) => write!(
+                f,
+                "line {}: \"{}\" has {} letters, words have to be {} to {} letters long",
+                self.line, self.entry, len, MIN_WORD_LENGTH, MAX_WORD_LENGTH
+            ),
+            Problem::NotLetters => write!(
+                f,
+                "line {}: \"{}\" has something other than the letters a to z in it",
+                self.line, self.entry
+            ),
+            Problem::Duplicate(first) => write!(
+                f,
+                "line {}: \"{}\" is already on line {}",
+                self.line, self.entry, first
+            ),
+        }
+    }
+}
+
+/// Every malformed entry in a word list file
+#[derive(Debug)]
+pub struct WordListError {
+    pub path: PathBuf,
+    pub entries: Vec<MalformedEntry>,
+}
+
+impl Display for WordListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} has malformed entries:", self.path.display())?;
+        for entry in &self.entries {
+            write!(f, "\n  {}", entry)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for WordListError {}
+
+fn read_words(path: &Path) -> Result<Vec<String>> {
+    let contents = fs::read_to_string(path)
+        .wrap_err_with(|| format!("couldn't read word list {}", path.display()))?;
+
+    parse_words(&contents).map_err(|entries| {
+        WordListError {
+            path: path.to_path_buf(),
+            entries,
+        }
+        .into()
+    })
+}
+
+/// Parses a word list with one word per line. Blank lines and lines starting with `#` are
+/// skipped, and words are lowercased. Every malformed line is reported, not just the first.
+///
+/// Words of any playable length are accepted, so one file can serve games of every length.
+pub fn parse_words(contents: &str) -> Result<Vec<String>, Vec<MalformedEntry>> {
+    let mut words = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut malformed = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let line_number = i + 1;
+        let entry = line.trim();
+        if entry.is_empty() || entry.starts_with('#') {
+            continue;
+        }
+
+        let word = entry.to_ascii_lowercase();
+        let problem = if !word.chars().all(|c| c.is_ascii_lowercase()) {
+            Some(Problem::NotLetters)
+        } else if !(MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&word.len()) {
+            Some(Problem::WrongLength(word.len()))
+        } else {
+            seen.get(&word).map(|first| Problem::Duplicate(*first))
+        };
+
+        match problem {
+            Some(problem) => malformed.push(MalformedEntry {
+                line: line_number,
+                entry: entry.to_string(),
+                problem,
+            }),
+            None => {
+                seen.insert(word.clone(), line_number);
+                words.push(word);
+            }
+        }
+    }
+
+    if malformed.is_empty() {
+        Ok(words)
+    } else {
+        Err(malformed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_words_are_stable() {
+        let words = WordList::builtin();
+
+        assert_eq!(words.daily_word(412), words.daily_word(412));
+        assert_ne!(words.daily_word(412), words.daily_word(413));
+    }
+
+    #[test]
+    fn daily_words_cycle_through_every_word() {
+        // 2315 is 5 * 463, so the stride has to skip over a few candidates for this list
+        for len in [VALID_WORDS.len(), 10, 1637 * 2] {
+            let answers: Vec<String> = (0..len).map(|i| format!("{:05}", i)).collect();
+            let words = WordList::new(answers, Vec::new());
+
+            let mut seen: Vec<String> = (0..len as u32).map(|n| words.daily_word(n)).collect();
+            seen.sort();
+            seen.dedup();
+
+            assert_eq!(seen.len(), len);
+        }
+    }
+
+    #[test]
+    fn answers_are_always_allowed() {
+        let words = WordList::new(vec![String::from("crwth")], vec![String::from("aahed")]);
+
+        assert!(words.check_validity("crwth"));
+        assert!(words.check_validity("aahed"));
+        assert!(!words.check_validity("rebus"));
+    }
+
+    #[test]
+    fn malformed_entries_are_reported_with_line_numbers() {
+        let contents = "# answers\ncrane\n\nslate\ncra\ncr4ne\nCRANE\nstares\nincomparable\n";
+
+        assert_eq!(
+            parse_words(contents),
+            Err(vec![
+                MalformedEntry {
+                    line: 5,
+                    entry: String::from("cra"),
+                    problem: Problem::WrongLength(3),
+                },
+                MalformedEntry {
+                    line: 6,
+                    entry: String::from("cr4ne"),
+                    problem: Problem::NotLetters,
+                },
+                MalformedEntry {
+                    line: 7,
+                    entry: String::from("CRANE"),
+                    problem: Problem::Duplicate(2),
+                },
+                MalformedEntry {
+                    line: 9,
+                    entry: String::from("incomparable"),
+                    problem: Problem::WrongLength(12),
+                },
+            ])
+        );
+        assert_eq!(
+            parse_words(" Crane \nslate\n"),
+            Ok(vec![String::from("crane"), String::from("slate")])
+        );
+    }
+
+    #[test]
+    fn lists_load_from_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let answers = dir.path().join("answers.txt");
+        let allowed = dir.path().join("allowed.txt");
+        fs::write(&answers, "crwth\n").unwrap();
+        fs::write(&allowed, "aahed\naah\n").unwrap();
+
+        let words = WordList::load(Some(&answers), None, 5).unwrap();
+        assert_eq!(words.answers(), ["crwth"]);
+        assert!(words.check_validity("rebus"));
+
+        let err = WordList::load(Some(&answers), Some(&allowed), 5).unwrap_err();
+        assert!(err.to_string().ends_with(
+            "allowed.txt has malformed entries:\n  line 2: \"aah\" has 3 letters, words have to be 4 to 11 letters long"
+        ));
+    }
+
+    #[test]
+    fn lists_are_filtered_to_the_word_length() {
+        let dir = tempfile::tempdir().unwrap();
+        let answers = dir.path().join("answers.txt");
+        fs::write(&answers, "crwth\nrustle\nfriday\n").unwrap();
+
+        let words = WordList::load(Some(&answers), None, 6).unwrap();
+        assert_eq!(words.answers(), ["rustle", "friday"]);
+        assert!(!words.check_validity("crwth"));
+        assert!(!words.check_validity("rebus"));
+
+        // The built-in list only has five letter words
+        assert!(WordList::load(None, None, 6).is_err());
+    }
 }
@@ -0,0 +1,794 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use eyre::{eyre, Result};
+use serde::Deserialize;
+
+use crate::app::code::PuzzleCode;
+use crate::app::daily::{self, Clock};
+use crate::app::options::{self, GameOptions, TimeLimit};
+use crate::app::share::ShareOptions;
+use crate::app::theme::Theme;
+use crate::app::words::{WordList, DEFAULT_WORD_LENGTH, MAX_WORD_LENGTH, MIN_WORD_LENGTH};
+use crate::config::Config;
+use crate::io::net;
+
+/// Like Wordle? Like using the terminal for everything? Rustle is a Wordle clone for the
+/// terminal.
+#[derive(Debug, Parser)]
+#[command(name = "rustle", version, about)]
+pub struct Cli {
+    /// Play in hard mode: green letters must stay put and yellow letters must be reused
+    #[arg(long)]
+    pub hard: bool,
+
+    /// Play today's daily puzzle, the same word for everyone
+    #[arg(long, conflicts_with_all = ["date", "puzzle"])]
+    pub daily: bool,
+
+    /// Play the daily puzzle from a past date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", conflicts_with = "puzzle")]
+    pub date: Option<NaiveDate>,
+
+    /// Play a past daily puzzle by its number
+    #[arg(long, value_name = "NUMBER")]
+    pub puzzle: Option<u32>,
+
+    /// Use orange and blue instead of green and yellow, the same as --theme color-blind
+    #[arg(long, alias = "colour-blind", conflicts_with_all = ["theme", "theme_file"])]
+    pub color_blind: bool,
+
+    /// The colours the board and keyboard are drawn in
+    #[arg(long, value_enum, value_name = "THEME")]
+    pub theme: Option<ThemeName>,
+
+    /// Draw the board and keyboard in colours read from this TOML file
+    #[arg(long, value_name = "PATH", conflicts_with = "theme")]
+    pub theme_file: Option<PathBuf>,
+
+    /// Play without colour, marking letters with brackets instead. The same as --theme
+    /// monochrome, which is also picked when NO_COLOR is set and no other theme is
+    #[arg(
+        long,
+        alias = "no-color",
+        conflicts_with_all = ["theme", "theme_file", "color_blind"]
+    )]
+    pub monochrome: bool,
+
+    /// Whether the NO_COLOR environment variable asks for no colour
+    #[arg(skip = no_color())]
+    pub no_color: bool,
+
+    /// Also write the shareable result to this file when the game ends
+    #[arg(long, value_name = "PATH")]
+    pub share_file: Option<PathBuf>,
+
+    /// Don't copy the shareable result to the clipboard
+    #[arg(long)]
+    pub no_clipboard: bool,
+
+    /// How many letters words have, from 4 to 11. Anything but 5 needs word lists with words
+    /// of that length
+    #[arg(
+        long,
+        value_name = "LETTERS",
+        default_value_t = DEFAULT_WORD_LENGTH,
+        value_parser = word_length
+    )]
+    pub length: usize,
+
+    /// Play 2, 4 or 8 words at once, like Dordle, Quordle and Octordle
+    #[arg(
+        long,
+        value_name = "BOARDS",
+        default_value_t = 1,
+        value_parser = PossibleValuesParser::new(["1", "2", "4", "8"])
+            .map(|boards| boards.parse::<usize>().unwrap())
+    )]
+    pub boards: usize,
+
+    /// How many guesses you get. The default is 6 for one board, and 7, 9 and 13 for 2, 4 and 8
+    /// boards
+    #[arg(
+        long,
+        value_name = "GUESSES",
+        value_parser = clap::value_parser!(u16).range(1..).map(usize::from),
+        conflicts_with = "unlimited"
+    )]
+    pub attempts: Option<usize>,
+
+    /// Keep guessing until you get it, for practice
+    #[arg(long)]
+    pub unlimited: bool,
+
+    /// Play Absurdle: there's no word until the game runs out of ways to dodge your guesses.
+    /// Guesses are unlimited unless --attempts is given
+    #[arg(long, conflicts_with_all = ["daily", "date", "puzzle", "boards"])]
+    pub absurdle: bool,
+
+    /// Show the best next guesses and how many words are still possible next to the board
+    #[arg(long)]
+    pub hints: bool,
+
+    /// Play the puzzle a friend made with `rustle create`
+    #[arg(
+        long,
+        value_name = "CODE",
+        conflicts_with_all = ["daily", "date", "puzzle", "absurdle", "boards", "length"]
+    )]
+    pub code: Option<String>,
+
+    /// Play with two people at one keyboard, taking turns to type a secret word for the other
+    /// to guess
+    #[arg(long, conflicts_with_all = ["daily", "date", "puzzle", "absurdle", "boards", "code"])]
+    pub hot_seat: bool,
+
+    /// Solve the word before the time is up, like 90s or 2m
+    #[arg(long, value_name = "TIME", value_parser = time, conflicts_with = "hot_seat")]
+    pub countdown: Option<Duration>,
+
+    /// Solve as many words as you can, one after another, before the time is up. Missing a word
+    /// ends the run too. Five minutes unless a time is given
+    #[arg(
+        long,
+        value_name = "TIME",
+        value_parser = time,
+        num_args = 0..=1,
+        default_missing_value = "5m",
+        conflicts_with_all = ["countdown", "daily", "date", "puzzle", "code", "absurdle", "hot_seat"]
+    )]
+    pub speedrun: Option<Duration>,
+
+    /// Play words one after another from a single pool of guesses, 30 unless a number is given.
+    /// Guesses a word doesn't need are saved for the next one, up to the usual number per word,
+    /// and the run is over once they're all used up
+    #[arg(
+        long,
+        value_name = "GUESSES",
+        value_parser = clap::value_parser!(u16).range(1..).map(usize::from),
+        num_args = 0..=1,
+        default_missing_value = "30",
+        conflicts_with_all = [
+            "daily", "date", "puzzle", "code", "absurdle", "boards", "unlimited", "hot_seat",
+            "countdown", "speedrun"
+        ]
+    )]
+    pub survival: Option<usize>,
+
+    /// Look back through finished games and replay them a row at a time, instead of playing
+    #[arg(long)]
+    pub history: bool,
+
+    /// Play a line at a time instead of on the full screen: guesses are read from standard input
+    /// and what each one revealed is printed in words, for screen readers and scripts
+    #[arg(long, conflicts_with_all = ["hot_seat", "history"])]
+    pub plain: bool,
+
+    /// Pick solutions from this file of words, one per line
+    #[arg(long, value_name = "PATH")]
+    pub answers: Option<PathBuf>,
+
+    /// Accept guesses from this file of words, one per line, on top of the answers
+    #[arg(long, value_name = "PATH")]
+    pub allowed: Option<PathBuf>,
+
+    /// Read settings from this file instead of the default config.toml
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Play every answer without the terminal UI and report how a solving strategy did. Word
+    /// list, length and attempts flags given before the subcommand still apply
+    Simulate(SimulateArgs),
+    /// Host a head-to-head game. Both players get the same word and see the colours of each
+    /// other's guesses as they go
+    Serve(ServeArgs),
+    /// Join a head-to-head game hosted with `rustle serve`
+    Join(JoinArgs),
+    /// Make a puzzle out of a word of your choosing, and print a code for a friend to play it
+    /// with `rustle --code`. --hard and --unlimited given before the subcommand are part of the
+    /// puzzle
+    Create(CreateArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CreateArgs {
+    /// The word to be guessed
+    pub word: String,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ServeArgs {
+    /// The address to listen on. 0.0.0.0 lets players on other machines join
+    #[arg(long, default_value = "127.0.0.1")]
+    pub bind: String,
+
+    #[arg(long, default_value_t = net::DEFAULT_PORT)]
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct JoinArgs {
+    /// Where the game is hosted
+    #[arg(value_name = "HOST:PORT", default_value_t = format!("127.0.0.1:{}", net::DEFAULT_PORT))]
+    pub address: String,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct SimulateArgs {
+    /// How to pick each guess
+    #[arg(long, value_enum, default_value_t = StrategyName::Entropy)]
+    pub strategy: StrategyName,
+
+    /// Only play this many answers, picked at random
+    #[arg(long, value_name = "GAMES")]
+    pub sample: Option<usize>,
+
+    /// Seed for picking the sample and for the random strategy, so runs can be repeated
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// Write a JSON report, or a CSV line per game
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    pub format: OutputFormat,
+
+    /// How many of the worst games to list in the report
+    #[arg(long, value_name = "GAMES", default_value_t = 10)]
+    pub worst: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StrategyName {
+    /// Play the guess expected to give the most information
+    Entropy,
+    /// Play any word that could still be the answer
+    Random,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+}
+
+/// The themes that come with Rustle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    /// Green and yellow
+    Default,
+    /// Orange and blue, which are easier to tell apart for colour blind players
+    #[value(alias = "colour-blind")]
+    #[serde(alias = "colour-blind")]
+    ColorBlind,
+    /// Bright colours with black letters on them
+    HighContrast,
+    /// No colour at all: [A] for a letter in the right spot, (A) for one in the word and a lower
+    /// case letter for one that isn't
+    Monochrome,
+}
+
+impl ThemeName {
+    pub fn theme(self) -> Theme {
+        match self {
+            ThemeName::Default => Theme::default(),
+            ThemeName::ColorBlind => Theme::color_blind(),
+            ThemeName::HighContrast => Theme::high_contrast(),
+            ThemeName::Monochrome => Theme::monochrome(),
+        }
+    }
+}
+
+impl Cli {
+    /// Turn the command line flags and config file into the rules for a game
+    pub fn game_options(&self, config: &Config, clock: &dyn Clock) -> Result<GameOptions> {
+        let code = self.code.as_deref().map(PuzzleCode::decode).transpose()?;
+
+        // A puzzle's word decides how long the words are
+        let word = match (&code, &self.command) {
+            (Some(code), _) => Some(code.word.clone()),
+            (None, Some(Command::Create(args))) => Some(args.word.to_lowercase()),
+            _ => None,
+        };
+        let length = word.as_ref().map_or(self.length, String::len);
+        if !(MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&length) {
+            return Err(eyre!(
+                "words have to be {} to {} letters long",
+                MIN_WORD_LENGTH,
+                MAX_WORD_LENGTH
+            ));
+        }
+
+        let answers = self.answers.as_ref().or(config.answers.as_ref());
+        let allowed = self.allowed.as_ref().or(config.allowed.as_ref());
+        let words = if answers.is_none() && allowed.is_none() && length == DEFAULT_WORD_LENGTH {
+            WordList::builtin()
+        } else {
+            Arc::new(WordList::load(
+                answers.map(PathBuf::as_path),
+                allowed.map(PathBuf::as_path),
+                length,
+            )?)
+        };
+
+        // Codes can only be checked for tampering so far, the word has to be one the game knows
+        if let Some(word) = &word {
+            if !words.check_validity(word) {
+                return Err(match code {
+                    Some(_) => eyre!(
+                        "this puzzle code is for a word that isn't in the word list, it might need the same word lists as whoever made it"
+                    ),
+                    None => eyre!("\"{}\" isn't in the word list", word),
+                });
+            }
+        }
+
+        let today = daily::todays_puzzle(clock);
+
+        let puzzle = if self.daily {
+            Some(today)
+        } else if let Some(date) = self.date {
+            let puzzle = daily::puzzle_number(date).ok_or_else(|| {
+                eyre!(
+                    "there was no puzzle on {}, the first one was on {}",
+                    date,
+                    daily::FIRST_PUZZLE_DATE
+                )
+            })?;
+            Some(puzzle)
+        } else {
+            self.puzzle
+        };
+
+        // Hints from one board can contradict another's, which would make a game unwinnable
+        if self.hard && self.boards > 1 {
+            return Err(eyre!("hard mode can only be played with a single board"));
+        }
+
+        // The host's word is the one both players get
+        if let Some(Command::Serve(_)) = self.command {
+            if self.boards > 1 || self.absurdle {
+                return Err(eyre!(
+                    "head-to-head games are played on a single board with a word picked up front"
+                ));
+            }
+        }
+
+        if (self.hot_seat || self.speedrun.is_some() || self.survival.is_some())
+            && matches!(self.command, Some(Command::Serve(_) | Command::Join(_)))
+        {
+            return Err(eyre!(
+                "hot seat games, speedruns and survival runs can't be played head to head"
+            ));
+        }
+
+        if self.plain && matches!(self.command, Some(Command::Serve(_) | Command::Join(_))) {
+            return Err(eyre!(
+                "head-to-head games need the full screen, they can't be played with --plain"
+            ));
+        }
+
+        if let Some(puzzle) = puzzle {
+            if puzzle > today {
+                return Err(eyre!(
+                    "puzzle #{} isn't out yet, today's is #{}",
+                    puzzle,
+                    today
+                ));
+            }
+        }
+
+        let code_unlimited = code.as_ref().is_some_and(|code| code.unlimited);
+
+        // A theme on the command line wins over the config file, which wins over NO_COLOR. A theme
+        // file wins over a theme name from the same place.
+        let theme_name = self
+            .theme
+            .or(self.color_blind.then_some(ThemeName::ColorBlind))
+            .or(self.monochrome.then_some(ThemeName::Monochrome));
+        let theme_file = match (&self.theme_file, theme_name) {
+            (Some(path), _) => Some(path),
+            (None, Some(_)) => None,
+            (None, None) => config.theme_file.as_ref(),
+        };
+        let theme_name = theme_name
+            .or(config.theme)
+            .or(self.no_color.then_some(ThemeName::Monochrome))
+            .unwrap_or(ThemeName::Default);
+        let theme = match theme_file {
+            Some(path) => Theme::load(path)?,
+            None => theme_name.theme(),
+        };
+
+        Ok(GameOptions {
+            hard_mode: self.hard || code.as_ref().is_some_and(|code| code.hard_mode),
+            puzzle,
+            word,
+            // Only the colour blind theme shares its result in its own colours
+            color_blind: theme_file.is_none() && theme_name == ThemeName::ColorBlind,
+            theme,
+            word_length: length,
+            boards: self.boards,
+            max_attempts: match (
+                self.attempts,
+                self.unlimited || self.absurdle || code_unlimited,
+            ) {
+                (Some(attempts), _) => Some(attempts),
+                (None, true) => None,
+                (None, false) => Some(options::default_max_attempts(self.boards)),
+            },
+            absurdle: self.absurdle,
+            hints: self.hints,
+            hot_seat: self.hot_seat,
+            time_limit: match (self.countdown, self.speedrun) {
+                (Some(limit), _) => Some(TimeLimit::Countdown(limit)),
+                (None, Some(limit)) => Some(TimeLimit::Speedrun(limit)),
+                (None, None) => None,
+            },
+            survival: self.survival,
+            words,
+        })
+    }
+
+    /// What to do with the result once the game is over
+    pub fn share_options(&self) -> ShareOptions {
+        ShareOptions {
+            file: self.share_file.clone(),
+            // The clipboard escape code would end up in whatever is reading the output
+            clipboard: !self.no_clipboard && !self.plain,
+        }
+    }
+}
+
+fn word_length(value: &str) -> Result<usize, String> {
+    let length: usize = value
+        .parse()
+        .map_err(|_| format!("{} isn't a number", value))?;
+
+    if (MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&length) {
+        Ok(length)
+    } else {
+        Err(format!(
+            "words have to be {} to {} letters long",
+            MIN_WORD_LENGTH, MAX_WORD_LENGTH
+        ))
+    }
+}
+
+/// NO_COLOR counts as set when it's anything but empty, see https://no-color.org
+fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// A length of time like 90s, 2m or 1m30s. A bare number is seconds.
+fn time(value: &str) -> Result<Duration, String> {
+    let invalid = || {
+        format!(
+            "{} isn't a time, try something like 90s, 2m or 1m30s",
+            value
+        )
+    };
+
+    let mut seconds = 0u64;
+    let mut number = String::new();
+    for character in value.chars() {
+        let unit = match character {
+            '0'..='9' => {
+                number.push(character);
+                continue;
+            }
+            'm' => 60,
+            's' => 1,
+            _ => return Err(invalid()),
+        };
+        let count: u64 = number.parse().map_err(|_| invalid())?;
+        seconds = seconds.saturating_add(count.saturating_mul(unit));
+        number.clear();
+    }
+    if !number.is_empty() {
+        let count: u64 = number.parse().map_err(|_| invalid())?;
+        seconds = seconds.saturating_add(count);
+    }
+
+    if seconds == 0 {
+        return Err(String::from("the time has to be more than zero"));
+    }
+
+    Ok(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::daily::FixedClock;
+    use crate::app::state::AppState;
+    use tui::style::Color;
+
+    fn options(args: &[&str]) -> Result<GameOptions> {
+        let clock = FixedClock(daily::puzzle_date(412).and_hms_opt(12, 0, 0).unwrap());
+
+        Cli::try_parse_from([&["rustle"], args].concat())?.game_options(&Config::default(), &clock)
+    }
+
+    #[test]
+    fn daily_flags_pick_a_puzzle() {
+        assert_eq!(options(&[]).unwrap().puzzle, None);
+        assert_eq!(options(&["--daily"]).unwrap().puzzle, Some(412));
+        assert_eq!(options(&["--puzzle", "7"]).unwrap().puzzle, Some(7));
+        assert_eq!(
+            options(&["--date", "2022-01-01"]).unwrap().puzzle,
+            Some(196)
+        );
+    }
+
+    #[test]
+    fn future_and_prehistoric_puzzles_are_rejected() {
+        assert!(options(&["--puzzle", "413"]).is_err());
+        assert!(options(&["--date", "2021-06-18"]).is_err());
+        assert!(options(&["--daily", "--puzzle", "3"]).is_err());
+    }
+
+    #[test]
+    fn word_length_is_limited() {
+        assert_eq!(options(&[]).unwrap().word_length, 5);
+        assert!(options(&["--length", "3"]).is_err());
+        assert!(options(&["--length", "12"]).is_err());
+        // There are no six letter words built in
+        assert!(options(&["--length", "6"]).is_err());
+    }
+
+    #[test]
+    fn attempts_can_be_limited_or_unlimited() {
+        assert_eq!(options(&[]).unwrap().max_attempts, Some(6));
+        assert_eq!(options(&["--attempts", "9"]).unwrap().max_attempts, Some(9));
+        assert_eq!(options(&["--unlimited"]).unwrap().max_attempts, None);
+        assert!(options(&["--attempts", "0"]).is_err());
+        assert!(options(&["--attempts", "9", "--unlimited"]).is_err());
+    }
+
+    #[test]
+    fn boards_get_more_attempts() {
+        let quordle = options(&["--boards", "4"]).unwrap();
+        assert_eq!(quordle.boards, 4);
+        assert_eq!(quordle.max_attempts, Some(9));
+
+        assert_eq!(
+            options(&["--boards", "8", "--attempts", "20"])
+                .unwrap()
+                .max_attempts,
+            Some(20)
+        );
+        assert!(options(&["--boards", "3"]).is_err());
+        assert!(options(&["--boards", "2", "--hard"]).is_err());
+    }
+
+    #[test]
+    fn absurdle_is_unlimited_and_single_board() {
+        let absurdle = options(&["--absurdle"]).unwrap();
+        assert!(absurdle.absurdle);
+        assert_eq!(absurdle.max_attempts, None);
+
+        assert_eq!(
+            options(&["--absurdle", "--attempts", "8"])
+                .unwrap()
+                .max_attempts,
+            Some(8)
+        );
+        assert!(options(&["--absurdle", "--daily"]).is_err());
+        assert!(options(&["--absurdle", "--boards", "2"]).is_err());
+    }
+
+    #[test]
+    fn simulate_takes_the_game_flags_before_it() {
+        let cli = Cli::try_parse_from(["rustle", "--attempts", "8", "simulate", "--format", "csv"])
+            .unwrap();
+
+        match cli.command {
+            Some(Command::Simulate(args)) => {
+                assert_eq!(args.format, OutputFormat::Csv);
+                assert_eq!(args.strategy, StrategyName::Entropy);
+            }
+            _ => panic!("not simulating"),
+        }
+        assert_eq!(cli.attempts, Some(8));
+    }
+
+    #[test]
+    fn head_to_head_is_single_board() {
+        assert!(options(&["serve"]).is_ok());
+        assert!(options(&["--boards", "2", "serve"]).is_err());
+        assert!(options(&["--absurdle", "serve"]).is_err());
+
+        let cli = Cli::try_parse_from(["rustle", "join"]).unwrap();
+        match cli.command {
+            Some(Command::Join(args)) => assert_eq!(args.address, "127.0.0.1:7878"),
+            _ => panic!("not joining"),
+        }
+    }
+
+    #[test]
+    fn puzzle_codes_play_the_word_they_were_made_for() {
+        let created = options(&["--hard", "create", "Those"]).unwrap();
+        assert_eq!(created.word.as_deref(), Some("those"));
+        let code = PuzzleCode {
+            word: String::from("those"),
+            hard_mode: true,
+            unlimited: false,
+        }
+        .encode();
+
+        let played = options(&["--code", &code]).unwrap();
+
+        assert_eq!(played.word.as_deref(), Some("those"));
+        assert!(played.hard_mode);
+        assert_eq!(played.max_attempts, Some(6));
+        assert_eq!(AppState::new(&played).boards[0].solution, "those");
+    }
+
+    #[test]
+    fn puzzle_codes_are_checked() {
+        let code = |word: &str| {
+            PuzzleCode {
+                word: word.to_string(),
+                hard_mode: false,
+                unlimited: true,
+            }
+            .encode()
+        };
+
+        assert_eq!(
+            options(&["--code", &code("quiet")]).unwrap().max_attempts,
+            None
+        );
+        // Made up words get through the code but not the word list
+        assert!(options(&["--code", &code("zzzzz")]).is_err());
+        assert!(options(&["--code", &code("quiet")[1..]]).is_err());
+        assert!(options(&["--code", &code("quiet"), "--daily"]).is_err());
+        assert!(options(&["create", "zzzzz"]).is_err());
+        assert!(options(&["create", "abc"]).is_err());
+    }
+
+    #[test]
+    fn times_can_be_given_in_minutes_and_seconds() {
+        let countdown =
+            |time: &str| options(&["--countdown", time]).map(|options| options.time_limit);
+
+        assert_eq!(
+            countdown("90").unwrap(),
+            Some(TimeLimit::Countdown(Duration::from_secs(90)))
+        );
+        assert_eq!(
+            countdown("2m").unwrap(),
+            Some(TimeLimit::Countdown(Duration::from_secs(120)))
+        );
+        assert_eq!(
+            countdown("1m30s").unwrap(),
+            Some(TimeLimit::Countdown(Duration::from_secs(90)))
+        );
+        assert!(countdown("0s").is_err());
+        assert!(countdown("m").is_err());
+        assert!(countdown("2h").is_err());
+    }
+
+    #[test]
+    fn speedruns_are_five_minutes_unless_told_otherwise() {
+        assert_eq!(options(&[]).unwrap().time_limit, None);
+        assert_eq!(
+            options(&["--speedrun"]).unwrap().time_limit,
+            Some(TimeLimit::Speedrun(Duration::from_secs(300)))
+        );
+        assert_eq!(
+            options(&["--speedrun", "60s"]).unwrap().time_limit,
+            Some(TimeLimit::Speedrun(Duration::from_secs(60)))
+        );
+        assert!(options(&["--speedrun", "--daily"]).is_err());
+        assert!(options(&["--speedrun", "--countdown", "2m"]).is_err());
+        assert!(options(&["--speedrun", "5m", "serve"]).is_err());
+    }
+
+    #[test]
+    fn survival_runs_start_with_thirty_guesses() {
+        assert_eq!(options(&[]).unwrap().survival, None);
+        assert_eq!(options(&["--survival"]).unwrap().survival, Some(30));
+        assert_eq!(
+            options(&["--survival", "12", "--attempts", "4"])
+                .unwrap()
+                .survival,
+            Some(12)
+        );
+        assert!(options(&["--survival", "0"]).is_err());
+        assert!(options(&["--survival", "--unlimited"]).is_err());
+        assert!(options(&["--survival", "--speedrun"]).is_err());
+    }
+
+    #[test]
+    fn hot_seat_picks_its_own_words() {
+        assert!(options(&["--hot-seat"]).unwrap().hot_seat);
+        assert!(options(&["--hot-seat", "--hard", "--length", "5"]).is_ok());
+        assert!(options(&["--hot-seat", "--daily"]).is_err());
+        assert!(options(&["--hot-seat", "--absurdle"]).is_err());
+        assert!(options(&["--hot-seat", "join"]).is_err());
+    }
+
+    #[test]
+    fn plain_games_are_played_alone() {
+        assert!(options(&["--plain", "--speedrun"]).is_ok());
+        assert!(options(&["--plain", "--hot-seat"]).is_err());
+        assert!(options(&["--plain", "serve"]).is_err());
+
+        let cli = Cli::try_parse_from(["rustle", "--plain"]).unwrap();
+        assert!(!cli.share_options().clipboard);
+    }
+
+    #[test]
+    fn themes_come_from_the_command_line_before_the_config_file() {
+        let clock = FixedClock(daily::puzzle_date(412).and_hms_opt(12, 0, 0).unwrap());
+        let dir = tempfile::tempdir().unwrap();
+        let theme_file = dir.path().join("theme.toml");
+        std::fs::write(&theme_file, "correct = \"magenta\"\n").unwrap();
+        let config = Config {
+            theme: Some(ThemeName::HighContrast),
+            ..Config::default()
+        };
+        // Whatever NO_COLOR is set to where the tests run is left out of it
+        let themed = |args: &[&str], config: &Config| {
+            let mut cli = Cli::try_parse_from([&["rustle"], args].concat()).unwrap();
+            cli.no_color = false;
+            cli.game_options(config, &clock).unwrap()
+        };
+
+        assert_eq!(themed(&[], &Config::default()).theme, Theme::default());
+        assert_eq!(themed(&[], &config).theme, Theme::high_contrast());
+
+        let color_blind = themed(&["--colour-blind"], &config);
+        assert_eq!(color_blind.theme, Theme::color_blind());
+        assert!(color_blind.color_blind);
+
+        let from_file = Config {
+            theme_file: Some(theme_file.clone()),
+            ..config.clone()
+        };
+        assert_eq!(themed(&[], &from_file).theme.correct, Color::Magenta);
+        assert_eq!(
+            themed(&["--theme", "default"], &from_file).theme,
+            Theme::default()
+        );
+        assert!(options(&["--color-blind", "--theme", "high-contrast"]).is_err());
+        assert!(options(&["--theme-file", "missing.toml"]).is_err());
+    }
+
+    #[test]
+    fn no_color_goes_monochrome_unless_a_theme_is_picked() {
+        let clock = FixedClock(daily::puzzle_date(412).and_hms_opt(12, 0, 0).unwrap());
+        let themed = |args: &[&str], config: &Config, no_color: bool| {
+            let mut cli = Cli::try_parse_from([&["rustle"], args].concat()).unwrap();
+            cli.no_color = no_color;
+            cli.game_options(config, &clock).unwrap().theme
+        };
+        let config = Config {
+            theme: Some(ThemeName::HighContrast),
+            ..Config::default()
+        };
+
+        assert_eq!(themed(&[], &Config::default(), true), Theme::monochrome());
+        assert_eq!(
+            themed(&["--no-color"], &Config::default(), false),
+            Theme::monochrome()
+        );
+        assert_eq!(themed(&[], &config, true), Theme::high_contrast());
+        assert_eq!(
+            themed(&["--theme", "default"], &Config::default(), true),
+            Theme::default()
+        );
+        assert!(options(&["--monochrome", "--color-blind"]).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eyre::{Result, WrapErr};
+use serde::Deserialize;
+
+use crate::cli::ThemeName;
+use crate::io::storage;
+
+/// Settings read from `config.toml`. Anything given on the command line wins over the file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Plain text file of words a solution can be picked from
+    pub answers: Option<PathBuf>,
+    /// Plain text file of words that are accepted as guesses
+    pub allowed: Option<PathBuf>,
+    /// One of the themes that come with Rustle
+    pub theme: Option<ThemeName>,
+    /// TOML file of colours to draw the game in, instead of `theme`
+    pub theme_file: Option<PathBuf>,
+}
+
+impl Config {
+    /// Reads the config file at `path`, or the default one if no path is given. Only the
+    /// default file is allowed to be missing.
+    pub fn load(path: Option<&Path>) -> Result<Config> {
+        let default_path = storage::config_path();
+        let path = path.unwrap_or(&default_path);
+
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if path == default_path && err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Config::default())
+            }
+            Err(err) => {
+                return Err(err).wrap_err_with(|| format!("couldn't read {}", path.display()))
+            }
+        };
+
+        let mut config: Config = toml::from_str(&contents)
+            .wrap_err_with(|| format!("couldn't parse {}", path.display()))?;
+        config.resolve_paths(path.parent().unwrap_or_else(|| Path::new(".")));
+
+        Ok(config)
+    }
+
+    /// Paths in the file are relative to the directory the file is in
+    fn resolve_paths(&mut self, dir: &Path) {
+        let paths = [&mut self.answers, &mut self.allowed, &mut self.theme_file];
+        for path in paths.into_iter().flatten() {
+            *path = dir.join(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_lists_are_relative_to_the_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "answers = \"answers.txt\"\nallowed = \"/usr/share/allowed.txt\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(Some(&path)).unwrap();
+
+        assert_eq!(config.answers, Some(dir.path().join("answers.txt")));
+        assert_eq!(
+            config.allowed,
+            Some(PathBuf::from("/usr/share/allowed.txt"))
+        );
+    }
+
+    #[test]
+    fn explicit_config_files_must_exist() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert!(Config::load(Some(&dir.path().join("missing.toml"))).is_err());
+    }
+}
@@ -9,15 +9,42 @@ use super::InputEvent;
 /// type is handled in its own thread and returned to a common `Receiver`
 pub struct Events {
     rx: tokio::sync::mpsc::Receiver<InputEvent>,
-    // Need to be kept around to prevent disposing the sender side.
-    _tx: tokio::sync::mpsc::Sender<InputEvent>,
+    // Need to be kept around to prevent disposing the sender side, and for `Interrupt`s
+    tx: tokio::sync::mpsc::Sender<InputEvent>,
     // To stop the loop
     stop_capture: Arc<AtomicBool>,
+    // Set when the game has to stop without a chance to clean up, see `Interrupt`
+    interrupted: Arc<AtomicBool>,
+}
+
+/// Stops the game from any thread, a panicking one included. The terminal is given back as soon
+/// as anything panics, and the game mustn't carry on drawing over it after that.
+#[derive(Clone)]
+pub struct Interrupt {
+    tx: tokio::sync::mpsc::Sender<InputEvent>,
+    interrupted: Arc<AtomicBool>,
+}
+
+impl Interrupt {
+    pub fn interrupt(&self) {
+        self.interrupted.store(true, Ordering::SeqCst);
+        // Wakes the game up in case the events thread is the one that panicked
+        let _ = self.tx.try_send(InputEvent::Tick);
+    }
 }
 
 impl Events {
     /// Constructs an new instance of `Events` with the default config.
     pub fn new(tick_rate: Duration) -> Events {
+        Events::from_source(tick_rate, read_terminal)
+    }
+
+    /// Events read from somewhere other than the terminal, as tests do. `source` waits for up to
+    /// the time it's given for something to happen, and there's a tick after every wait.
+    pub fn from_source<F>(tick_rate: Duration, mut source: F) -> Events
+    where
+        F: FnMut(Duration) -> Option<InputEvent> + Send + 'static,
+    {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let stop_capture = Arc::new(AtomicBool::new(false));
 
@@ -25,16 +52,10 @@ impl Events {
         let event_stop_capture = stop_capture.clone();
         tokio::spawn(async move {
             loop {
-                // poll for tick rate duration, if no event, sent tick event.
-                if crossterm::event::poll(tick_rate).unwrap() {
-                    if let crossterm::event::Event::Key(key) = crossterm::event::read().unwrap() {
-                        let key = Key::from(key);
-                        if let Err(_err) = event_tx.send(InputEvent::Input(key)).await {
-                        }
-                    }
-                }
-                if let Err(_err) = event_tx.send(InputEvent::Tick).await {
+                if let Some(event) = source(tick_rate) {
+                    if let Err(_err) = event_tx.send(event).await {}
                 }
+                if let Err(_err) = event_tx.send(InputEvent::Tick).await {}
                 if event_stop_capture.load(Ordering::Relaxed) {
                     break;
                 }
@@ -43,8 +64,9 @@ impl Events {
 
         Events {
             rx,
-            _tx: tx,
+            tx,
             stop_capture,
+            interrupted: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -53,8 +75,41 @@ impl Events {
         self.rx.recv().await.unwrap_or(InputEvent::Tick)
     }
 
+    /// A handle for stopping the game from elsewhere
+    pub fn interrupt(&self) -> Interrupt {
+        Interrupt {
+            tx: self.tx.clone(),
+            interrupted: Arc::clone(&self.interrupted),
+        }
+    }
+
+    /// Whether the game was stopped with an `Interrupt`
+    pub fn interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::SeqCst)
+    }
+
     /// Close
     pub fn close(&mut self) {
         self.stop_capture.store(true, Ordering::Relaxed)
     }
 }
+
+/// Stops the events thread however the game ends, an error included
+impl Drop for Events {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// Waits for up to `tick_rate` for a key press or a resize
+fn read_terminal(tick_rate: Duration) -> Option<InputEvent> {
+    if !crossterm::event::poll(tick_rate).unwrap() {
+        return None;
+    }
+
+    match crossterm::event::read().unwrap() {
+        crossterm::event::Event::Key(key) => Some(InputEvent::Input(Key::from(key))),
+        crossterm::event::Event::Resize(width, height) => Some(InputEvent::Resize(width, height)),
+        _ => None,
+    }
+}
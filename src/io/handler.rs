@@ -1,39 +1,217 @@
 use std::sync::Arc;
-use std::time::Duration;
 
 use eyre::Result;
 
-use super::IoEvent;
+use super::net::{Message, Peer};
+use super::{storage, IoEvent};
+use crate::app::daily::{self, SystemClock};
+use crate::app::history::{self, HistoryEntry};
+use crate::app::save::{decode_row, encode_row, SavedGame};
+use crate::app::state::GameStatus;
+use crate::app::stats::Stats;
+use crate::app::versus::PlayerStatus;
 use crate::app::App;
 
 /// In the IO thread, we handle IO event without blocking the UI thread
 pub struct IoAsyncHandler {
     app: Arc<tokio::sync::Mutex<App>>,
+    /// The other player in a head-to-head game
+    peer: Option<Peer>,
 }
 
 impl IoAsyncHandler {
     pub fn new(app: Arc<tokio::sync::Mutex<App>>) -> Self {
-        Self { app }
+        Self { app, peer: None }
+    }
+
+    /// Passes messages for the other player on to them
+    pub fn with_peer(mut self, peer: Peer) -> Self {
+        self.peer = Some(peer);
+        self
     }
 
     /// We could be async here
     pub async fn handle_io_event(&mut self, io_event: IoEvent) {
+        // Events from the other player weren't dispatched by the app, so it isn't waiting on them
+        let dispatched = !matches!(
+            io_event,
+            IoEvent::OpponentJoined | IoEvent::Received(_) | IoEvent::OpponentLeft
+        );
         let result = match io_event {
             IoEvent::Initialize => self.do_initialize().await,
+            IoEvent::SaveStats => self.do_save_stats().await,
+            IoEvent::Analyse => self.do_analyse().await,
+            IoEvent::RecordHistory(entry) => self.do_record_history(&entry).await,
+            IoEvent::OpponentJoined => self.do_opponent_joined().await,
+            IoEvent::Received(message) => self.do_received(message).await,
+            IoEvent::OpponentLeft => {
+                self.app.lock().await.opponent_left();
+                Ok(())
+            }
+            IoEvent::SendProgress => self.do_send_progress().await,
+            IoEvent::Hints => self.do_hints().await,
         };
 
-        if let Err(_err) = result {
-        }
+        if let Err(_err) = result {}
 
-        let _app = self.app.lock().await;
+        if dispatched {
+            self.app.lock().await.loaded();
+        }
     }
 
-    /// We use dummy implementation here, just wait 1s
+    /// Load the player's stats and any unfinished game, and start the first game
     async fn do_initialize(&mut self) -> Result<()> {
+        let viewing_history = self.app.lock().await.history().is_some();
+        if viewing_history {
+            let entries = history::load(&storage::history_path())?;
+            self.app.lock().await.history_loaded(entries);
+
+            return Ok(());
+        }
+
+        // Stats or saves that can't be read shouldn't keep anyone from playing
+        let stats = Stats::load(&storage::stats_path()).unwrap_or_default();
+        let save_path = storage::save_path(self.app.lock().await.options());
+        let saved = SavedGame::load(&save_path).unwrap_or_default();
+        let todays_puzzle = daily::todays_puzzle(&SystemClock);
+
+        // Ranking the opening guesses takes a moment, so it's done here rather than while the
+        // UI is waiting on the app
+        let (solver, hints) = {
+            let app = self.app.lock().await;
+            (app.solver(), app.options().hints)
+        };
+        if hints {
+            tokio::task::spawn_blocking(move || solver.suggestions(&[], &[])).await?;
+        }
+
         let mut app = self.app.lock().await;
-        tokio::time::sleep(Duration::from_secs(1)).await;
-        app.initialized(); // we could update the app state
+        // Every kind of game has a save of its own, but one for a different day's puzzle or a
+        // different code stays put until a game of its kind is quit partway and takes its
+        // place. One that fits is only kept in memory from here on, and saved again if it's quit
+        // again. Games that don't keep saves leave it alone too.
+        let saved = saved.filter(|saved| saved.fits(app.options()) && app.keeps_saved_games());
+        let resuming = saved.is_some();
+        app.initialized(stats, saved, todays_puzzle);
+
+        if resuming {
+            storage::remove(&save_path)?;
+        }
 
         Ok(())
     }
+
+    async fn do_save_stats(&mut self) -> Result<()> {
+        let stats = self.app.lock().await.stats().clone();
+
+        stats.save(&storage::stats_path())
+    }
+
+    /// The analysis has to rank every guess for every row, which is too slow to do while the UI
+    /// is waiting on the app
+    async fn do_analyse(&mut self) -> Result<()> {
+        let (solver, guesses, rows) = {
+            let app = self.app.lock().await;
+            let state = app.state();
+            // Only the first board is analysed when there are several
+            (
+                app.solver(),
+                state.guesses.clone(),
+                state.boards[0].square_statuses.clone(),
+            )
+        };
+
+        let analysis = tokio::task::spawn_blocking(move || solver.analyse(&guesses, &rows)).await?;
+        self.app.lock().await.analysed(analysis);
+
+        Ok(())
+    }
+
+    /// Ranking every allowed guess against every answer still left is too slow to do while the
+    /// UI is waiting on the app
+    async fn do_hints(&mut self) -> Result<()> {
+        let (solver, question) = {
+            let app = self.app.lock().await;
+            (app.solver(), app.hints_question())
+        };
+
+        let (question, candidates, ranking) = tokio::task::spawn_blocking(move || {
+            let (guesses, rows) = &question;
+            let candidates = solver.candidates(guesses, rows).len();
+            let ranking = solver.suggestions(guesses, rows);
+
+            (question, candidates, ranking)
+        })
+        .await?;
+        self.app
+            .lock()
+            .await
+            .hints_ranked(question, candidates, ranking);
+
+        Ok(())
+    }
+
+    async fn do_record_history(&mut self, entry: &HistoryEntry) -> Result<()> {
+        history::append(&storage::history_path(), entry)
+    }
+
+    /// The host's game starts once the guest is there, and the guest is sent its word
+    async fn do_opponent_joined(&mut self) -> Result<()> {
+        let start = {
+            let mut app = self.app.lock().await;
+            app.opponent_joined();
+
+            Message::Start {
+                solution: app.state().boards[0].solution.clone(),
+                max_attempts: app.state().max_attempts,
+            }
+        };
+
+        self.send(start).await
+    }
+
+    async fn do_received(&mut self, message: Message) -> Result<()> {
+        let mut app = self.app.lock().await;
+
+        match message {
+            Message::Start {
+                solution,
+                max_attempts,
+            } => app.versus_started(solution, max_attempts),
+            Message::Progress { rows, status } => {
+                app.opponent_progress(rows.iter().map(|row| decode_row(row)).collect(), status)
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn do_send_progress(&mut self) -> Result<()> {
+        let progress = {
+            let app = self.app.lock().await;
+            let state = app.state();
+
+            Message::Progress {
+                rows: state.boards[0]
+                    .square_statuses
+                    .iter()
+                    .map(|colors| encode_row(colors))
+                    .collect(),
+                status: match state.game_status {
+                    GameStatus::InProgress => PlayerStatus::Playing,
+                    GameStatus::Win => PlayerStatus::Won,
+                    GameStatus::Lose => PlayerStatus::Lost,
+                },
+            }
+        };
+
+        self.send(progress).await
+    }
+
+    async fn send(&self, message: Message) -> Result<()> {
+        match &self.peer {
+            Some(peer) => peer.send(message).await,
+            None => Ok(()),
+        }
+    }
 }
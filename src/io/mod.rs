@@ -1,6 +1,18 @@
+use crate::app::history::HistoryEntry;
+
 pub mod handler;
-// For this dummy application we only need two IO event
+pub mod net;
+pub mod storage;
+
 #[derive(Debug, Clone)]
 pub enum IoEvent {
-    Initialize, // Launch to initialize the application
+    Initialize,                       // Launch to initialize the application
+    SaveStats,                        // Write the stats out once a game has finished
+    Analyse,                          // Compare every guess of a finished game with the solver's
+    RecordHistory(Box<HistoryEntry>), // Add a finished game to the history file
+    OpponentJoined,                   // Someone joined the game being hosted
+    Received(net::Message),           // The other player sent something
+    OpponentLeft,                     // The other player's connection closed
+    SendProgress,                     // Tell the other player how the game is going
+    Hints,                            // Rank the next guesses for the hint panel
 }
@@ -0,0 +1,112 @@
+use std::net::SocketAddr;
+
+use eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+use super::IoEvent;
+use crate::app::versus::PlayerStatus;
+
+/// The port `rustle serve` listens on and `rustle join` connects to by default
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// What the two players tell each other, sent as a line of JSON each
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// Sent by the host once the guest has joined: the word they're both after
+    Start {
+        solution: String,
+        max_attempts: Option<usize>,
+    },
+    /// A player's board after every guess. Only the colours are sent, in the same `G`, `Y`
+    /// and `-` rows as saved games, so the letters stay secret.
+    Progress {
+        rows: Vec<String>,
+        status: PlayerStatus,
+    },
+}
+
+/// Sends messages to the other player, once there is one
+#[derive(Debug, Clone)]
+pub struct Peer {
+    outgoing: mpsc::Sender<Message>,
+}
+
+impl Peer {
+    pub async fn send(&self, message: Message) -> Result<()> {
+        self.outgoing
+            .send(message)
+            .await
+            .map_err(|_| eyre::eyre!("the connection to the other player is closed"))
+    }
+}
+
+/// Starts listening for a guest. Once one connects, `IoEvent::OpponentJoined` is sent and
+/// anything they send arrives as `IoEvent::Received`.
+pub async fn host(address: &str, io_tx: mpsc::Sender<IoEvent>) -> Result<(SocketAddr, Peer)> {
+    let listener = TcpListener::bind(address)
+        .await
+        .wrap_err_with(|| format!("couldn't listen on {}", address))?;
+    let local_address = listener.local_addr()?;
+    let (outgoing, outgoing_rx) = mpsc::channel(16);
+
+    tokio::spawn(async move {
+        // Only one guest is needed, so the listener is done with after that
+        if let Ok((stream, _)) = listener.accept().await {
+            if io_tx.send(IoEvent::OpponentJoined).await.is_ok() {
+                pump(stream, io_tx, outgoing_rx);
+            }
+        }
+    });
+
+    Ok((local_address, Peer { outgoing }))
+}
+
+/// Connects to a host started with `rustle serve`
+pub async fn join(address: &str, io_tx: mpsc::Sender<IoEvent>) -> Result<Peer> {
+    let stream = TcpStream::connect(address)
+        .await
+        .wrap_err_with(|| format!("couldn't connect to a game at {}", address))?;
+    let (outgoing, outgoing_rx) = mpsc::channel(16);
+
+    pump(stream, io_tx, outgoing_rx);
+
+    Ok(Peer { outgoing })
+}
+
+/// Passes lines from the connection on to the IO handler, and messages for the other player on
+/// to the connection, each in their own task so neither ever holds up the UI
+fn pump(stream: TcpStream, io_tx: mpsc::Sender<IoEvent>, mut outgoing_rx: mpsc::Receiver<Message>) {
+    let (reader, mut writer) = stream.into_split();
+
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+
+        while let Ok(Some(line)) = lines.next_line().await {
+            // Anything that isn't a message is ignored rather than ending the game
+            if let Ok(message) = serde_json::from_str(&line) {
+                if io_tx.send(IoEvent::Received(message)).await.is_err() {
+                    return;
+                }
+            }
+        }
+
+        let _ = io_tx.send(IoEvent::OpponentLeft).await;
+    });
+
+    tokio::spawn(async move {
+        while let Some(message) = outgoing_rx.recv().await {
+            let Ok(mut line) = serde_json::to_string(&message) else {
+                continue;
+            };
+            line.push('\n');
+
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    });
+}
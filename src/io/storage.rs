@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eyre::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::app::options::GameOptions;
+use crate::app::save::SavedGame;
+
+/// Setting this environment variable keeps all of Rustle's files in the given directory
+pub const DATA_DIR_VAR: &str = "RUSTLE_DATA_DIR";
+
+/// Where Rustle keeps its files, `~/.local/share/rustle` on Linux
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(DATA_DIR_VAR) {
+        return PathBuf::from(dir);
+    }
+
+    dirs::data_dir()
+        .map(|dir| dir.join("rustle"))
+        .unwrap_or_else(|| PathBuf::from(".rustle"))
+}
+
+/// Where the config file is looked for, `~/.config/rustle/config.toml` on Linux
+pub fn config_path() -> PathBuf {
+    dirs::config_dir()
+        .map(|dir| dir.join("rustle"))
+        .unwrap_or_else(|| PathBuf::from(".rustle"))
+        .join("config.toml")
+}
+
+pub fn stats_path() -> PathBuf {
+    data_dir().join("stats.json")
+}
+
+/// Where an unfinished game of this kind is kept between runs
+pub fn save_path(options: &GameOptions) -> PathBuf {
+    data_dir().join(SavedGame::file_name(options))
+}
+
+/// Where every finished game is logged, a line of JSON each
+pub fn history_path() -> PathBuf {
+    data_dir().join("history.jsonl")
+}
+
+/// Reads a JSON file, treating a missing file as empty.
+///
+/// A file that can't be understood is moved aside to `<name>.bak` and the default value is
+/// returned in its place, so the next save replaces it instead of the game refusing to start.
+pub fn load_or_backup<T>(path: &Path) -> Result<T>
+where
+    T: DeserializeOwned + Default,
+{
+    let contents = match fs::read(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(err) => return Err(err.into()),
+    };
+
+    match serde_json::from_slice(&contents) {
+        Ok(value) => Ok(value),
+        Err(_err) => {
+            fs::rename(path, backup_path(path))?;
+
+            Ok(T::default())
+        }
+    }
+}
+
+/// Writes a value as JSON. The file is written next to its destination first and then moved
+/// into place, so a crash halfway through can't leave half a file behind.
+pub fn save<T>(path: &Path, value: &T) -> Result<()>
+where
+    T: Serialize,
+{
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, serde_json::to_vec_pretty(value)?)?;
+    fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+/// Deletes a file, if it's there at all
+pub fn remove(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+
+    path.with_file_name(name)
+}
@@ -1,33 +1,37 @@
-use std::io::stdout;
+use std::io::{stdin, stdout};
 use std::sync::Arc;
-use std::time::Duration;
 
 use app::{App, AppReturn};
-use eyre::Result;
+use eyre::{eyre, Result};
 use inputs::events::Events;
 use inputs::InputEvent;
 use io::IoEvent;
-use tui::backend::CrosstermBackend;
-use tui::Terminal;
+use terminal::{Screen, TerminalGuard};
+use tui::layout::Rect;
 
 use crate::app::ui;
 
 pub mod app;
+pub mod cli;
+pub mod config;
 pub mod inputs;
 pub mod io;
+pub mod plain;
+pub mod simulate;
+pub mod terminal;
 
-pub async fn start_ui(app: &Arc<tokio::sync::Mutex<App>>) -> Result<()> {
-    // Configure Crossterm backend for tui
-    let stdout = stdout();
-    crossterm::terminal::enable_raw_mode()?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-    terminal.clear()?;
-    terminal.hide_cursor()?;
-
-    // User event handler
-    let tick_rate = Duration::from_millis(200);
-    let mut events = Events::new(tick_rate);
+/// Plays on `backend` until the game is quit, taking over the terminal while it does. The game is
+/// stopped early if `events` is interrupted, since the terminal has been given back by then.
+pub async fn start_ui<B>(
+    app: &Arc<tokio::sync::Mutex<App>>,
+    backend: B,
+    mut events: Events,
+) -> Result<()>
+where
+    B: Screen,
+{
+    // The terminal is restored when this goes out of scope, on every way out of this function
+    let mut terminal = TerminalGuard::new(backend)?;
 
     // Trigger state change from Init to Initialized
     {
@@ -39,6 +43,11 @@ pub async fn start_ui(app: &Arc<tokio::sync::Mutex<App>>) -> Result<()> {
     loop {
         let mut app = app.lock().await;
 
+        // The terminal was given back after a panic, so there's nothing left to draw on
+        if events.interrupted() {
+            return Err(eyre!("Rustle stopped after a panic, see the message above"));
+        }
+
         // Render
         terminal.draw(|rect| ui::draw(rect, &app))?;
 
@@ -46,6 +55,11 @@ pub async fn start_ui(app: &Arc<tokio::sync::Mutex<App>>) -> Result<()> {
         let result = match events.next().await {
             InputEvent::Input(key) => app.do_action(key).await,
             InputEvent::Tick => app.update_on_tick().await,
+            // Redrawn straight away at the new size, which may call for a different layout
+            InputEvent::Resize(width, height) => {
+                terminal.resize(Rect::new(0, 0, width, height))?;
+                AppReturn::Continue
+            }
         };
         // Check if we should exit
         if result == AppReturn::Exit {
@@ -54,10 +68,18 @@ pub async fn start_ui(app: &Arc<tokio::sync::Mutex<App>>) -> Result<()> {
         }
     }
 
-    // Restore the terminal and close application
-    terminal.clear()?;
-    terminal.show_cursor()?;
-    crossterm::terminal::disable_raw_mode()?;
+    Ok(())
+}
+
+/// Plays a line at a time on standard input and output instead of on the full screen
+pub async fn start_plain(app: &Arc<tokio::sync::Mutex<App>>) -> Result<()> {
+    app.lock().await.dispatch(IoEvent::Initialize).await;
+    plain::wait_for_io(app).await;
+
+    plain::play(app, stdin().lock(), &mut stdout()).await?;
+
+    // The stats and history are saved before there's any chance of exiting
+    plain::wait_for_io(app).await;
 
     Ok(())
 }
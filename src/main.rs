@@ -1,28 +1,121 @@
+use std::io::stdout;
 use std::sync::Arc;
+use std::time::Duration;
 
+use clap::Parser;
 use eyre::Result;
+use rustle::app::code::PuzzleCode;
+use rustle::app::daily::SystemClock;
+use rustle::app::share;
+use rustle::app::versus::Role;
 use rustle::app::App;
+use rustle::cli::{Cli, Command};
+use rustle::config::Config;
+use rustle::inputs::events::Events;
 use rustle::io::handler::IoAsyncHandler;
-use rustle::io::IoEvent;
-use rustle::start_ui;
+use rustle::io::{net, storage, IoEvent};
+use rustle::{simulate, start_plain, start_ui, terminal};
+use tui::backend::CrosstermBackend;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let config = Config::load(cli.config.as_deref())?;
+    let options = cli.game_options(&config, &SystemClock)?;
+
+    match &cli.command {
+        Some(Command::Simulate(args)) => {
+            let report = simulate::run(&options, args, &mut std::io::stdout().lock())?;
+            eprintln!("{}", report.summary());
+
+            return Ok(());
+        }
+        Some(Command::Create(_)) => {
+            let code = PuzzleCode {
+                word: options.word.clone().unwrap_or_default(),
+                hard_mode: options.hard_mode,
+                unlimited: options.max_attempts.is_none(),
+            };
+            println!("{}", code.encode());
+            eprintln!(
+                "Share the code, and it can be played with: rustle --code {}",
+                code.encode()
+            );
+
+            return Ok(());
+        }
+        _ => {}
+    }
+
     let (sync_io_tx, mut sync_io_rx) = tokio::sync::mpsc::channel::<IoEvent>(100);
 
+    let mut app = App::new(sync_io_tx.clone(), options);
+    if cli.history {
+        app.view_history();
+    }
+
+    let peer = match &cli.command {
+        Some(Command::Serve(args)) => {
+            let address = format!("{}:{}", args.bind, args.port);
+            let (address, peer) = net::host(&address, sync_io_tx.clone()).await?;
+            app.start_versus(Role::Host, address.to_string());
+            Some(peer)
+        }
+        Some(Command::Join(args)) => {
+            let peer = net::join(&args.address, sync_io_tx.clone()).await?;
+            app.start_versus(Role::Guest, args.address.clone());
+            Some(peer)
+        }
+        _ => None,
+    };
+
     // We need to share the App between thread
-    let app = Arc::new(tokio::sync::Mutex::new(App::new(sync_io_tx.clone())));
+    let app = Arc::new(tokio::sync::Mutex::new(app));
     let app_ui = Arc::clone(&app);
 
     // Handle IO in a specifc thread
     tokio::spawn(async move {
         let mut handler = IoAsyncHandler::new(app);
+        if let Some(peer) = peer {
+            handler = handler.with_peer(peer);
+        }
         while let Some(io_event) = sync_io_rx.recv().await {
             handler.handle_io_event(io_event).await;
         }
     });
 
-    start_ui(&app_ui).await?;
+    if cli.plain {
+        start_plain(&app_ui).await?;
+    } else {
+        let events = Events::new(Duration::from_millis(200));
+
+        // A panic puts the terminal back before its message is printed, so the message can be
+        // read. The panic could be in another thread, like the IO one, so the game is stopped too.
+        let interrupt = events.interrupt();
+        terminal::set_panic_hook(move || {
+            let _ = terminal::restore(&mut CrosstermBackend::new(stdout()));
+            interrupt.interrupt();
+        });
+
+        start_ui(&app_ui, CrosstermBackend::new(stdout()), events).await?;
+    }
+
+    let app = app_ui.lock().await;
+
+    // The terminal is back to normal, if it was ever taken over, so the result can be printed for
+    // sharing. A speedrun is more than the one word it ended on, so it isn't shared.
+    let shared = app
+        .speedrun()
+        .is_none()
+        .then(|| share::share_text(app.state()));
+    if let Some(text) = shared.flatten() {
+        share::publish(&text, &cli.share_options())?;
+    }
+
+    // A game quit halfway through is kept for next time
+    if let Some(saved) = app.saved_game() {
+        saved.save(&storage::save_path(app.options()))?;
+    }
 
     Ok(())
 }
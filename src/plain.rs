@@ -0,0 +1,300 @@
+use std::io::{BufRead, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+use eyre::Result;
+use tokio::sync::Mutex;
+
+use crate::app::state::{AppState, GameStatus, LetterStatus};
+use crate::app::App;
+use crate::inputs::key::Key;
+
+/// Typed instead of a guess to hear what's known about every letter so far
+pub const LETTERS_COMMAND: &str = "/letters";
+/// Typed instead of a guess to stop playing. An unfinished game is kept like it is on the full
+/// screen, and so it is when the input runs out.
+pub const QUIT_COMMAND: &str = "/quit";
+
+/// Plays a line at a time, without drawing anything: every line read is a guess, and what it
+/// revealed is written out in words. The game is the same one `App` plays on the full screen,
+/// played with the same keys, so the app has to be initialized first.
+pub async fn play<R, W>(app: &Arc<Mutex<App>>, input: R, output: &mut W) -> Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut lines = input.lines();
+
+    // The game left unfinished last time is offered first, like on the full screen
+    loop {
+        let offer = app.lock().await.resume_offer().map(|saved| {
+            let game = match saved.puzzle_number {
+                Some(puzzle) => format!("Rustle #{}", puzzle),
+                None => String::from("a game"),
+            };
+            format!(
+                "You left {} unfinished after {}. Carry on with it? Type y to carry on or n for a new game.",
+                game,
+                guesses(saved.guesses.len())
+            )
+        });
+        match offer {
+            Some(offer) => writeln!(output, "{}", offer)?,
+            None => break,
+        }
+
+        let answer = match lines.next() {
+            Some(line) => line?.trim().to_lowercase(),
+            None => return Ok(()),
+        };
+        let key = match answer.as_str() {
+            "" | "y" | "yes" => Key::Char('y'),
+            "n" | "no" => Key::Char('n'),
+            _ => continue,
+        };
+        app.lock().await.do_action(key).await;
+    }
+
+    {
+        let app = app.lock().await;
+        writeln!(output, "{}", introduction(&app))?;
+        writeln!(
+            output,
+            "Type a guess and press Enter. Type {} to hear what's known about each letter, or {} to stop.",
+            LETTERS_COMMAND, QUIT_COMMAND
+        )?;
+
+        // A resumed game goes over the guesses it already had
+        let state = app.state();
+        for guess in 0..state.guesses.len() {
+            writeln!(output, "{}", describe_guess(state, guess))?;
+        }
+    }
+
+    for line in lines {
+        let line = line?.trim().to_lowercase();
+        let mut app = app.lock().await;
+
+        // The clock could have run out while the guess was being typed
+        app.update_on_tick().await;
+        if app.state().game_status != GameStatus::InProgress {
+            writeln!(output, "{}", ending(&app))?;
+            break;
+        }
+
+        match line.as_str() {
+            "" => continue,
+            LETTERS_COMMAND => {
+                writeln!(output, "{}", describe_letters(app.state()))?;
+                continue;
+            }
+            QUIT_COMMAND => break,
+            _ => {}
+        }
+        if !line.chars().all(|letter| letter.is_ascii_lowercase()) {
+            writeln!(output, "Guesses can only have the letters A to Z")?;
+            continue;
+        }
+
+        // A rejected guess stays in the input, so it has to go before the next one is typed
+        while !app.state().input.is_empty() {
+            app.do_action(Key::Backspace).await;
+        }
+        for letter in line.chars() {
+            app.do_action(Key::Char(letter)).await;
+        }
+        app.do_action(Key::Enter).await;
+
+        if let Some(problem) = &app.state().message {
+            writeln!(output, "{}", problem)?;
+            continue;
+        }
+        let state = app.state();
+        writeln!(output, "{}", describe_guess(state, state.guesses.len() - 1))?;
+
+        // Speedruns and survival runs go straight on to the next word, so how this one went has
+        // to be noted before it's gone
+        let solved = state.all_solved();
+        let solution = state.boards[0].solution.clone();
+        app.update_on_tick().await;
+
+        if app.state().game_status != GameStatus::InProgress {
+            writeln!(output, "{}", ending(&app))?;
+            break;
+        }
+        if app.state().guesses.is_empty() {
+            let previous = match solved {
+                true => String::from("Solved!"),
+                false => format!("Out of guesses, the word was {}.", solution.to_uppercase()),
+            };
+            writeln!(output, "{} Next word. {}", previous, introduction(&app))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Waits until the IO thread has handled everything sent to it, like the stats to be saved at
+/// the end of a game
+pub async fn wait_for_io(app: &Arc<Mutex<App>>) {
+    while app.lock().await.is_loading() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+/// What the word to guess is like, and what there is to guess it with
+fn introduction(app: &App) -> String {
+    let state = app.state();
+
+    let mut text = match state.boards.len() {
+        1 => format!("Guess the {} letter word", state.word_length),
+        boards => format!(
+            "Guess all {} of the {} letter words at once",
+            boards, state.word_length
+        ),
+    };
+    match state.max_attempts {
+        Some(attempts) => text.push_str(&format!(" in {}.", guesses(attempts))),
+        None => text.push('.'),
+    }
+
+    if state.hard_mode {
+        text.push_str(" Hard mode: every hint has to be used in the guesses after it.");
+    }
+    if let Some(survival) = app.survival() {
+        text.push_str(&format!(
+            " {} left for the rest of the run.",
+            guesses(survival.pool)
+        ));
+    }
+    if let Some(left) = app.time_left() {
+        text.push_str(&format!(" {} left.", duration(left)));
+    }
+
+    text
+}
+
+/// What a guess revealed about each of its letters, like "Guess 2 of 6, ROUND: R correct, O
+/// absent, U present, N absent, D absent". With several boards each one gets its own line.
+fn describe_guess(state: &AppState, guess: usize) -> String {
+    let word = &state.guesses[guess];
+    let mut text = match state.max_attempts {
+        Some(attempts) => format!(
+            "Guess {} of {}, {}",
+            guess + 1,
+            attempts,
+            word.to_uppercase()
+        ),
+        None => format!("Guess {}, {}", guess + 1, word.to_uppercase()),
+    };
+
+    // Boards only get a row for each guess made while they were unsolved
+    let row = |board: usize| state.boards[board].square_statuses.get(guess);
+    if state.boards.len() == 1 {
+        if let Some(row) = row(0) {
+            text.push_str(&format!(": {}", describe_row(word, row)));
+        }
+
+        return text;
+    }
+
+    for board in 0..state.boards.len() {
+        let feedback = match row(board) {
+            Some(row) => describe_row(word, row),
+            None => String::from("solved already"),
+        };
+        text.push_str(&format!("\nBoard {}: {}", board + 1, feedback));
+    }
+
+    text
+}
+
+fn describe_row(word: &str, row: &[LetterStatus]) -> String {
+    word.chars()
+        .zip(row)
+        .map(|(letter, status)| format!("{} {}", letter.to_ascii_uppercase(), status_name(*status)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Every letter by what's known about it, going by the first board that isn't solved yet like
+/// the keyboard does
+fn describe_letters(state: &AppState) -> String {
+    let board = state
+        .boards
+        .iter()
+        .find(|board| !board.is_solved())
+        .unwrap_or(&state.boards[0]);
+
+    [
+        ("Correct", LetterStatus::Correct),
+        ("Present", LetterStatus::Present),
+        ("Absent", LetterStatus::Absent),
+        ("Not guessed yet", LetterStatus::Unknown),
+    ]
+    .into_iter()
+    .filter_map(|(name, status)| {
+        let letters: Vec<String> = ('a'..='z')
+            .filter(|letter| board.key_statuses.get(letter) == Some(&status))
+            .map(|letter| letter.to_ascii_uppercase().to_string())
+            .collect();
+
+        (!letters.is_empty()).then(|| format!("{}: {}.", name, letters.join(", ")))
+    })
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn status_name(status: LetterStatus) -> &'static str {
+    match status {
+        LetterStatus::Correct => "correct",
+        LetterStatus::Present => "present",
+        LetterStatus::Absent => "absent",
+        LetterStatus::Unknown => "unknown",
+    }
+}
+
+/// How the game went, and the stats for games that count towards them
+fn ending(app: &App) -> String {
+    let mut text = app.result().unwrap_or_default();
+
+    if app.counts_towards_stats() {
+        let stats = app.stats();
+        text.push_str(&format!(
+            "\nPlayed {}, won {}%, current streak {}, best streak {}.",
+            stats.played,
+            stats.win_percentage(),
+            stats.current_streak,
+            stats.max_streak
+        ));
+    }
+
+    text
+}
+
+/// "1 guess" or "2 guesses"
+fn guesses(count: usize) -> String {
+    match count {
+        1 => String::from("1 guess"),
+        count => format!("{} guesses", count),
+    }
+}
+
+/// Minutes and seconds, spelled out to be read aloud
+fn duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let unit = |count: u64, name: &str| match count {
+        1 => format!("1 {}", name),
+        count => format!("{} {}s", count, name),
+    };
+
+    match (seconds / 60, seconds % 60) {
+        (0, seconds) => unit(seconds, "second"),
+        (minutes, 0) => unit(minutes, "minute"),
+        (minutes, seconds) => format!(
+            "{} and {}",
+            unit(minutes, "minute"),
+            unit(seconds, "second")
+        ),
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+
+use eyre::Result;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::Serialize;
+
+use crate::app::options::GameOptions;
+use crate::app::solver::{self, Pattern, Solver};
+use crate::app::state::{AppState, LetterStatus};
+use crate::cli::{OutputFormat, SimulateArgs, StrategyName};
+
+/// One guess and the colours it got
+#[derive(Debug, Clone, PartialEq)]
+pub struct Turn {
+    pub guess: String,
+    pub colors: Vec<LetterStatus>,
+}
+
+/// A way of playing the game without anyone at the keyboard
+pub trait Strategy {
+    /// What the strategy is called in reports
+    fn name(&self) -> &str;
+
+    /// The next word to guess, given every guess so far and the colours it got
+    fn next_guess(&mut self, history: &[Turn]) -> String;
+}
+
+/// Always plays the guess the solver expects to give the most information
+pub struct EntropyStrategy {
+    solver: Arc<Solver>,
+    /// Games that have gone the same way so far get the same guess, so each one is only worked
+    /// out once
+    cache: HashMap<Vec<(String, Pattern)>, String>,
+}
+
+impl EntropyStrategy {
+    pub fn new(solver: Arc<Solver>) -> EntropyStrategy {
+        EntropyStrategy {
+            solver,
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl Strategy for EntropyStrategy {
+    fn name(&self) -> &str {
+        "entropy"
+    }
+
+    fn next_guess(&mut self, history: &[Turn]) -> String {
+        let key = history
+            .iter()
+            .map(|turn| (turn.guess.clone(), solver::pattern_of(&turn.colors)))
+            .collect();
+        let solver = &self.solver;
+
+        self.cache
+            .entry(key)
+            .or_insert_with(|| {
+                let (guesses, rows) = split(history);
+                solver.suggestions(&guesses, &rows)[0].word.clone()
+            })
+            .clone()
+    }
+}
+
+/// Guesses any word that could still be the answer, like a player who ignores strategy
+pub struct RandomStrategy {
+    solver: Arc<Solver>,
+    rng: StdRng,
+}
+
+impl RandomStrategy {
+    pub fn new(solver: Arc<Solver>, seed: u64) -> RandomStrategy {
+        RandomStrategy {
+            solver,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomStrategy {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn next_guess(&mut self, history: &[Turn]) -> String {
+        let (guesses, rows) = split(history);
+        // The history can only rule out every answer if the strategy was fed the wrong colours,
+        // and then any answer is as good as another
+        let candidates = match self.solver.candidates(&guesses, &rows) {
+            candidates if candidates.is_empty() => self.solver.candidates(&[], &[]),
+            candidates => candidates,
+        };
+
+        candidates.choose(&mut self.rng).unwrap().to_string()
+    }
+}
+
+fn split(history: &[Turn]) -> (Vec<String>, Vec<Vec<LetterStatus>>) {
+    history
+        .iter()
+        .map(|turn| (turn.guess.clone(), turn.colors.clone()))
+        .unzip()
+}
+
+/// How a strategy did against one answer
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameResult {
+    pub answer: String,
+    pub guesses: Vec<String>,
+    pub solved: bool,
+}
+
+/// How a strategy did across every game it played
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub strategy: String,
+    pub games: usize,
+    pub solved: usize,
+    pub failure_rate: f64,
+    /// The average number of guesses over the games that were solved
+    pub average_guesses: Option<f64>,
+    /// How many games were solved in 1 guess, 2 guesses and so on
+    pub distribution: Vec<usize>,
+    /// The games that took the most guesses, with failures first
+    pub worst: Vec<GameResult>,
+    pub results: Vec<GameResult>,
+}
+
+impl Report {
+    pub fn new(strategy: &str, results: Vec<GameResult>, worst_count: usize) -> Report {
+        let solved: Vec<&GameResult> = results.iter().filter(|result| result.solved).collect();
+
+        let mut distribution = Vec::new();
+        for result in &solved {
+            let guesses = result.guesses.len();
+            if distribution.len() < guesses {
+                distribution.resize(guesses, 0);
+            }
+            distribution[guesses - 1] += 1;
+        }
+
+        let mut worst = results.clone();
+        worst.sort_by_key(|result| (result.solved, std::cmp::Reverse(result.guesses.len())));
+        worst.truncate(worst_count);
+
+        Report {
+            strategy: strategy.to_string(),
+            games: results.len(),
+            solved: solved.len(),
+            failure_rate: if results.is_empty() {
+                0.0
+            } else {
+                (results.len() - solved.len()) as f64 / results.len() as f64
+            },
+            average_guesses: (!solved.is_empty()).then(|| {
+                solved
+                    .iter()
+                    .map(|result| result.guesses.len())
+                    .sum::<usize>() as f64
+                    / solved.len() as f64
+            }),
+            distribution,
+            worst,
+            results,
+        }
+    }
+
+    /// A line per game, for spreadsheets and plotting
+    pub fn write_csv(&self, out: &mut dyn Write) -> Result<()> {
+        writeln!(out, "strategy,answer,solved,guess_count,guesses")?;
+        for result in &self.results {
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                self.strategy,
+                result.answer,
+                result.solved,
+                result.guesses.len(),
+                result.guesses.join(" ")
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// The numbers that matter at a glance
+    pub fn summary(&self) -> String {
+        let average = match self.average_guesses {
+            Some(average) => format!("{:.3}", average),
+            None => String::from("-"),
+        };
+        let distribution: Vec<String> = self
+            .distribution
+            .iter()
+            .enumerate()
+            .map(|(i, count)| format!("{}: {}", i + 1, count))
+            .collect();
+
+        format!(
+            "{} games with the {} strategy, {} solved ({:.2}% failed), {} guesses on average\n{}",
+            self.games,
+            self.strategy,
+            self.solved,
+            self.failure_rate * 100.0,
+            average,
+            distribution.join(", ")
+        )
+    }
+}
+
+/// Plays a single game against `answer`, scoring guesses the same way the game does
+pub fn play(answer: &str, strategy: &mut dyn Strategy, max_attempts: Option<usize>) -> GameResult {
+    let mut state = AppState {
+        max_attempts,
+        ..AppState::with_solution(answer)
+    };
+    let mut history = Vec::new();
+
+    loop {
+        let guess = strategy.next_guess(&history);
+        state.guesses.push(guess.clone());
+        state.check_word_status();
+        state.increment_attempt();
+
+        let colors = state.boards[0].square_statuses.last().cloned().unwrap();
+        history.push(Turn { guess, colors });
+
+        if state.all_solved() || state.out_of_attempts() {
+            return GameResult {
+                answer: answer.to_string(),
+                solved: state.all_solved(),
+                guesses: state.guesses,
+            };
+        }
+    }
+}
+
+/// Plays every answer, or a seeded sample of them, and writes out the report
+pub fn run(options: &GameOptions, args: &SimulateArgs, out: &mut dyn Write) -> Result<Report> {
+    let solver = Arc::new(Solver::new(&options.words));
+    let mut strategy: Box<dyn Strategy> = match args.strategy {
+        StrategyName::Entropy => Box::new(EntropyStrategy::new(solver)),
+        StrategyName::Random => Box::new(RandomStrategy::new(solver, args.seed)),
+    };
+
+    let mut answers: Vec<&String> = options.words.answers().iter().collect();
+    if let Some(sample) = args.sample {
+        let mut rng = StdRng::seed_from_u64(args.seed);
+        answers = answers.choose_multiple(&mut rng, sample).copied().collect();
+    }
+
+    let results = answers
+        .into_iter()
+        .map(|answer| play(answer, strategy.as_mut(), options.max_attempts))
+        .collect();
+    let report = Report::new(strategy.name(), results, args.worst);
+
+    match args.format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, &report)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Csv => report.write_csv(out)?,
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::words::WordList;
+
+    fn options(answers: &[&str]) -> GameOptions {
+        GameOptions {
+            words: Arc::new(WordList::new(
+                answers.iter().map(|word| word.to_string()).collect(),
+                Vec::new(),
+            )),
+            ..GameOptions::default()
+        }
+    }
+
+    fn args(strategy: StrategyName, format: OutputFormat) -> SimulateArgs {
+        SimulateArgs {
+            strategy,
+            sample: None,
+            seed: 0,
+            format,
+            worst: 2,
+        }
+    }
+
+    /// Always guesses the same words, whatever the colours
+    struct Stubborn(Vec<&'static str>);
+
+    impl Strategy for Stubborn {
+        fn name(&self) -> &str {
+            "stubborn"
+        }
+
+        fn next_guess(&mut self, history: &[Turn]) -> String {
+            self.0[history.len() % self.0.len()].to_string()
+        }
+    }
+
+    #[test]
+    fn games_end_when_solved_or_out_of_guesses() {
+        let mut strategy = Stubborn(vec!["crane", "those"]);
+
+        let solved = play("those", &mut strategy, Some(6));
+        assert_eq!(solved.guesses, vec!["crane", "those"]);
+        assert!(solved.solved);
+
+        let failed = play("geese", &mut strategy, Some(3));
+        assert_eq!(failed.guesses, vec!["crane", "those", "crane"]);
+        assert!(!failed.solved);
+    }
+
+    #[test]
+    fn reports_add_up() {
+        let results = ["crane", "those", "geese", "batch"]
+            .iter()
+            .map(|answer| play(answer, &mut Stubborn(vec!["crane", "those"]), Some(3)))
+            .collect();
+
+        let report = Report::new("stubborn", results, 2);
+
+        assert_eq!(report.games, 4);
+        assert_eq!(report.solved, 2);
+        assert_eq!(report.failure_rate, 0.5);
+        assert_eq!(report.average_guesses, Some(1.5));
+        assert_eq!(report.distribution, vec![1, 1]);
+        let worst: Vec<&str> = report.worst.iter().map(|r| r.answer.as_str()).collect();
+        assert_eq!(worst, vec!["geese", "batch"]);
+    }
+
+    #[test]
+    fn the_solver_solves_everything() {
+        let options = options(&["batch", "catch", "hatch", "latch", "crane", "those"]);
+        let mut out = Vec::new();
+
+        let report = run(
+            &options,
+            &args(StrategyName::Entropy, OutputFormat::Json),
+            &mut out,
+        )
+        .unwrap();
+
+        assert_eq!(report.solved, 6);
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["strategy"], "entropy");
+        assert_eq!(json["results"].as_array().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn samples_and_random_guesses_are_repeatable() {
+        let options = options(&["batch", "catch", "hatch", "latch", "crane", "those"]);
+        let args = SimulateArgs {
+            sample: Some(3),
+            seed: 7,
+            ..args(StrategyName::Random, OutputFormat::Csv)
+        };
+
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+        run(&options, &args, &mut first).unwrap();
+        run(&options, &args, &mut second).unwrap();
+
+        let csv = String::from_utf8(first.clone()).unwrap();
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.starts_with("strategy,answer,solved,guess_count,guesses\nrandom,"));
+        assert_eq!(first, second);
+    }
+}
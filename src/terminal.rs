@@ -0,0 +1,129 @@
+use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+use std::panic;
+
+use crossterm::execute;
+use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
+use tui::backend::{Backend, CrosstermBackend};
+use tui::Terminal;
+
+/// A backend the game can take over while it runs. Drawing is left to `Backend`, and this covers
+/// the modes that have to be switched back off afterwards.
+pub trait Screen: Backend {
+    /// Raw mode hands every key press straight to the game, without echoing it
+    fn set_raw_mode(&mut self, enabled: bool) -> io::Result<()>;
+
+    /// The alternate screen keeps the game out of the shell's scrollback
+    fn set_alternate_screen(&mut self, enabled: bool) -> io::Result<()>;
+}
+
+impl<W> Screen for CrosstermBackend<W>
+where
+    W: Write,
+{
+    fn set_raw_mode(&mut self, enabled: bool) -> io::Result<()> {
+        let result = if enabled {
+            crossterm::terminal::enable_raw_mode()
+        } else {
+            crossterm::terminal::disable_raw_mode()
+        };
+
+        result.map_err(|error| io::Error::other(error.to_string()))
+    }
+
+    fn set_alternate_screen(&mut self, enabled: bool) -> io::Result<()> {
+        let result = if enabled {
+            execute!(self, EnterAlternateScreen)
+        } else {
+            execute!(self, LeaveAlternateScreen)
+        };
+
+        result.map_err(|error| io::Error::other(error.to_string()))
+    }
+}
+
+/// The terminal while the game has it. It's put back the way it was found when the guard is
+/// dropped, which happens however `start_ui` ends: by quitting, with an error or in a panic.
+pub struct TerminalGuard<B>
+where
+    B: Screen,
+{
+    terminal: Terminal<B>,
+}
+
+impl<B> TerminalGuard<B>
+where
+    B: Screen,
+{
+    /// Switches to raw mode and the alternate screen, with the cursor hidden
+    pub fn new(backend: B) -> io::Result<TerminalGuard<B>> {
+        let mut terminal = Terminal::new(backend)?;
+        terminal.backend_mut().set_raw_mode(true)?;
+
+        // From here on anything that goes wrong is undone by the guard
+        let mut guard = TerminalGuard { terminal };
+        guard.terminal.backend_mut().set_alternate_screen(true)?;
+        guard.terminal.hide_cursor()?;
+        guard.terminal.clear()?;
+
+        Ok(guard)
+    }
+}
+
+impl<B> Deref for TerminalGuard<B>
+where
+    B: Screen,
+{
+    type Target = Terminal<B>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terminal
+    }
+}
+
+impl<B> DerefMut for TerminalGuard<B>
+where
+    B: Screen,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.terminal
+    }
+}
+
+impl<B> Drop for TerminalGuard<B>
+where
+    B: Screen,
+{
+    fn drop(&mut self) {
+        if let Err(err) = restore(self.terminal.backend_mut()) {
+            eprintln!("Failed to restore the terminal: {}", err);
+        }
+    }
+}
+
+/// Leaves the alternate screen, shows the cursor and turns raw mode off. Every step is tried even
+/// if an earlier one fails, and doing it twice is harmless.
+pub fn restore<B>(backend: &mut B) -> io::Result<()>
+where
+    B: Screen,
+{
+    let left = backend.set_alternate_screen(false);
+    let shown = backend.show_cursor();
+    let cooked = backend.set_raw_mode(false);
+
+    left.and(shown).and(cooked)
+}
+
+/// Runs `restore` before the panic hook that was there already. Without it the panic message would
+/// be printed to the alternate screen in raw mode and vanish, since the guard is only dropped once
+/// the message has been printed.
+pub fn set_panic_hook<F>(restore: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        restore();
+        previous(info);
+    }));
+}
@@ -0,0 +1,51 @@
+//! Games for the integration tests to play: with a known answer, with IO events that go
+//! nowhere, and played a guess at a time
+
+// Every test file uses its own share of these
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use rustle::app::options::GameOptions;
+use rustle::app::stats::Stats;
+use rustle::app::words::WordList;
+use rustle::app::App;
+use rustle::inputs::key::Key;
+use rustle::io::IoEvent;
+use tokio::sync::mpsc;
+
+/// Somewhere to send IO events that are never handled
+pub fn io_sink() -> mpsc::Sender<IoEvent> {
+    let (io_tx, mut io_rx) = mpsc::channel(100);
+    tokio::spawn(async move { while io_rx.recv().await.is_some() {} });
+
+    io_tx
+}
+
+/// The options with "those" as the only answer. Every built-in answer can still be guessed.
+pub fn only_those(options: GameOptions) -> GameOptions {
+    let mut allowed = WordList::builtin().answers().to_vec();
+    allowed.push(String::from("those"));
+
+    GameOptions {
+        words: Arc::new(WordList::new(vec![String::from("those")], allowed)),
+        ..options
+    }
+}
+
+/// A game of "those" started with the options, whose IO events go nowhere
+pub fn start(options: GameOptions) -> App {
+    let mut app = App::new(io_sink(), only_those(options));
+    app.initialized(Stats::default(), None, 1);
+
+    app
+}
+
+/// Types the word in and submits it, then lets the game catch up like a tick would
+pub async fn guess(app: &mut App, word: &str) {
+    for letter in word.chars() {
+        app.do_action(Key::Char(letter)).await;
+    }
+    app.do_action(Key::Enter).await;
+    app.update_on_tick().await;
+}
@@ -0,0 +1,175 @@
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use rustle::app::options::GameOptions;
+use rustle::app::state::{score_guess, GameStatus};
+use rustle::app::versus::{Connection, PlayerStatus, Role};
+use rustle::app::words::WordList;
+use rustle::app::App;
+use rustle::io::handler::IoAsyncHandler;
+use rustle::io::net::{self, Peer};
+use rustle::io::IoEvent;
+use tempfile::TempDir;
+use tokio::sync::{mpsc, Mutex};
+
+mod common;
+
+type SharedApp = Arc<Mutex<App>>;
+
+/// An app like `main` sets up, minus the terminal
+fn start(options: &GameOptions) -> (SharedApp, mpsc::Sender<IoEvent>, mpsc::Receiver<IoEvent>) {
+    let (io_tx, io_rx) = mpsc::channel(100);
+    let app = Arc::new(Mutex::new(App::new(io_tx.clone(), options.clone())));
+
+    (app, io_tx, io_rx)
+}
+
+fn handle(app: &SharedApp, peer: Peer, mut io_rx: mpsc::Receiver<IoEvent>) {
+    let mut handler = IoAsyncHandler::new(Arc::clone(app)).with_peer(peer);
+
+    tokio::spawn(async move {
+        while let Some(io_event) = io_rx.recv().await {
+            handler.handle_io_event(io_event).await;
+        }
+    });
+}
+
+async fn guess(app: &SharedApp, word: &str) {
+    common::guess(&mut *app.lock().await, word).await;
+}
+
+/// Waits for something the other player did to arrive
+async fn eventually(app: &SharedApp, check: impl Fn(&App) -> bool) {
+    for _ in 0..250 {
+        if check(&*app.lock().await) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    panic!("gave up waiting on the other player");
+}
+
+/// Hosts a game and has a guest join it, once the guest has heard from the host
+async fn connect(
+    host_options: &GameOptions,
+    guest_options: &GameOptions,
+) -> (SharedApp, SharedApp) {
+    static DATA_DIR: OnceLock<TempDir> = OnceLock::new();
+    let data_dir = DATA_DIR.get_or_init(|| tempfile::tempdir().unwrap());
+    std::env::set_var("RUSTLE_DATA_DIR", data_dir.path());
+
+    let (host, host_tx, host_rx) = start(host_options);
+    let (address, host_peer) = net::host("127.0.0.1:0", host_tx.clone()).await.unwrap();
+    host.lock()
+        .await
+        .start_versus(Role::Host, address.to_string());
+    handle(&host, host_peer, host_rx);
+    host_tx.send(IoEvent::Initialize).await.unwrap();
+
+    let (guest, guest_tx, guest_rx) = start(guest_options);
+    let guest_peer = net::join(&address.to_string(), guest_tx.clone())
+        .await
+        .unwrap();
+    guest
+        .lock()
+        .await
+        .start_versus(Role::Guest, address.to_string());
+    handle(&guest, guest_peer, guest_rx);
+    guest_tx.send(IoEvent::Initialize).await.unwrap();
+
+    eventually(&guest, |app| {
+        app.versus().unwrap().connection != Connection::Waiting
+    })
+    .await;
+
+    (host, guest)
+}
+
+/// A host that always picks "those"
+fn hosting_those() -> GameOptions {
+    common::only_those(GameOptions::default())
+}
+
+#[tokio::test]
+async fn both_players_race_for_the_same_word() {
+    // The guest's answers leave the word out, so it can only know the word from the host. It's
+    // still allowed as a guess, as it would be with the same word lists at both ends.
+    let answers: Vec<String> = WordList::builtin()
+        .answers()
+        .iter()
+        .filter(|word| *word != "those")
+        .cloned()
+        .collect();
+    let guest_options = GameOptions {
+        words: Arc::new(WordList::new(answers, vec![String::from("those")])),
+        ..GameOptions::default()
+    };
+    let (host, guest) = connect(&hosting_those(), &guest_options).await;
+
+    assert_eq!(
+        guest.lock().await.versus().unwrap().connection,
+        Connection::Playing
+    );
+    assert_eq!(guest.lock().await.state().boards[0].solution, "those");
+    assert_eq!(
+        host.lock().await.versus().unwrap().connection,
+        Connection::Playing
+    );
+
+    guess(&host, "crane").await;
+    eventually(&guest, |app| app.versus().unwrap().opponent_rows.len() == 1).await;
+    assert_eq!(
+        guest.lock().await.versus().unwrap().opponent_rows[0],
+        score_guess("crane", "those")
+    );
+
+    guess(&guest, "those").await;
+    assert!(guest.lock().await.state().game_status == GameStatus::Win);
+    eventually(&host, |app| {
+        app.versus().unwrap().opponent_status == PlayerStatus::Won
+    })
+    .await;
+    assert_eq!(
+        host.lock().await.versus().unwrap().describe_opponent(),
+        "won in 1"
+    );
+}
+
+#[tokio::test]
+async fn guests_without_the_hosts_word_are_told_instead_of_playing() {
+    let answers: Vec<String> = WordList::builtin()
+        .answers()
+        .iter()
+        .filter(|word| *word != "those")
+        .cloned()
+        .collect();
+    let guest_options = GameOptions {
+        words: Arc::new(WordList::new(answers, Vec::new())),
+        ..GameOptions::default()
+    };
+    let (_host, guest) = connect(&hosting_those(), &guest_options).await;
+
+    let guest = guest.lock().await;
+    let versus = guest.versus().unwrap();
+    assert_eq!(versus.connection, Connection::Mismatched);
+    assert!(versus.mismatch.as_ref().unwrap().contains("word list"));
+    assert_ne!(guest.state().boards[0].solution, "those");
+}
+
+#[tokio::test]
+async fn guests_playing_another_length_are_told_instead_of_playing() {
+    let planet = vec![String::from("planet")];
+    let host_options = GameOptions {
+        word_length: 6,
+        words: Arc::new(WordList::new(planet.clone(), planet)),
+        ..GameOptions::default()
+    };
+    let (_host, guest) = connect(&host_options, &GameOptions::default()).await;
+
+    let guest = guest.lock().await;
+    let versus = guest.versus().unwrap();
+    assert_eq!(versus.connection, Connection::Mismatched);
+    assert!(versus.mismatch.as_ref().unwrap().contains("6 letter words"));
+    assert_eq!(guest.state().word_length, 5);
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::guess;
+use rustle::app::options::GameOptions;
+use rustle::app::solver::Suggestion;
+use rustle::app::stats::Stats;
+use rustle::app::App;
+use rustle::io::handler::IoAsyncHandler;
+use tokio::sync::{mpsc, Mutex};
+
+mod common;
+
+fn hinted() -> GameOptions {
+    GameOptions {
+        word: Some(String::from("those")),
+        hints: true,
+        ..GameOptions::default()
+    }
+}
+
+fn suggestion(word: &str) -> Suggestion {
+    Suggestion {
+        word: String::from(word),
+        entropy: 1.0,
+        candidate: false,
+    }
+}
+
+#[tokio::test]
+async fn hints_are_worked_out_while_the_game_goes_on() {
+    let (io_tx, mut io_rx) = mpsc::channel(100);
+    let app = Arc::new(Mutex::new(App::new(io_tx, hinted())));
+    let mut handler = IoAsyncHandler::new(Arc::clone(&app));
+    tokio::spawn(async move {
+        while let Some(io_event) = io_rx.recv().await {
+            handler.handle_io_event(io_event).await;
+        }
+    });
+
+    {
+        let mut app = app.lock().await;
+        app.initialized(Stats::default(), None, 1);
+        guess(&mut app, "crane").await;
+
+        // The guess is in without waiting on the solver
+        assert_eq!(app.state().guesses, vec!["crane"]);
+        assert!(app.hints_pending());
+    }
+
+    for _ in 0..250 {
+        if !app.lock().await.hints_pending() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    let app = app.lock().await;
+    let (guesses, rows) = app.hints_question();
+    assert!(!app.hints_pending());
+    assert_eq!(
+        app.hints().unwrap(),
+        &app.solver()
+            .hints(&guesses, &rows, app.hints().unwrap().suggestions.len())
+    );
+}
+
+#[tokio::test]
+async fn hints_for_an_earlier_guess_are_dropped() {
+    let mut app = App::new(
+        common::io_sink(),
+        GameOptions {
+            hard_mode: true,
+            ..hinted()
+        },
+    );
+    app.initialized(Stats::default(), None, 1);
+    let opening = app.hints_question();
+    guess(&mut app, "crane").await;
+
+    app.hints_ranked(opening, 2315, vec![suggestion("slate")]);
+    assert_eq!(app.hints(), None);
+    assert!(app.hints_pending());
+
+    // Hard mode has to use the E from "crane", so "sooty" is left out
+    app.hints_ranked(
+        app.hints_question(),
+        2,
+        vec![suggestion("sooty"), suggestion("those")],
+    );
+    assert_eq!(app.hints().unwrap().candidates, 2);
+    assert_eq!(app.hints().unwrap().suggestions, vec![suggestion("those")]);
+    assert!(!app.hints_pending());
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use common::guess;
+use rustle::app::history::HistoryEntry;
+use rustle::app::options::{GameOptions, TimeLimit};
+use rustle::app::stats::Stats;
+use rustle::app::App;
+use rustle::io::IoEvent;
+use tokio::sync::mpsc;
+
+mod common;
+
+/// A game of "those", and the channel its IO events go down
+fn start(options: GameOptions) -> (App, mpsc::Receiver<IoEvent>) {
+    let (io_tx, io_rx) = mpsc::channel(100);
+    let mut app = App::new(io_tx, common::only_those(options));
+    app.initialized(Stats::default(), None, 1);
+
+    (app, io_rx)
+}
+
+/// Every game sent to the history file so far
+fn recorded(io_rx: &mut mpsc::Receiver<IoEvent>) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    while let Ok(io_event) = io_rx.try_recv() {
+        if let IoEvent::RecordHistory(entry) = io_event {
+            entries.push(*entry);
+        }
+    }
+
+    entries
+}
+
+#[tokio::test]
+async fn every_word_of_a_speedrun_goes_in_the_history() {
+    let (mut app, mut io_rx) = start(GameOptions {
+        time_limit: Some(TimeLimit::Speedrun(Duration::from_secs(300))),
+        ..GameOptions::default()
+    });
+
+    guess(&mut app, "crane").await;
+    guess(&mut app, "those").await;
+    for _ in 0..6 {
+        guess(&mut app, "crane").await;
+    }
+
+    let entries = recorded(&mut io_rx);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].guesses, vec!["crane", "those"]);
+    assert!(entries[0].won);
+    assert_eq!(entries[1].guesses, vec!["crane"; 6]);
+    assert!(!entries[1].won);
+    assert!(entries.iter().all(|entry| entry.mode == "Random, Speedrun"));
+}
+
+#[tokio::test]
+async fn every_word_of_a_survival_run_goes_in_the_history() {
+    let (mut app, mut io_rx) = start(GameOptions {
+        survival: Some(7),
+        ..GameOptions::default()
+    });
+
+    guess(&mut app, "those").await;
+    for _ in 0..6 {
+        guess(&mut app, "crane").await;
+    }
+
+    let entries = recorded(&mut io_rx);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].guesses, vec!["those"]);
+    assert!(entries[0].won);
+    assert_eq!(entries[1].guesses, vec!["crane"; 6]);
+    assert!(!entries[1].won);
+    assert!(entries.iter().all(|entry| entry.mode == "Random, Survival"));
+}
+
+#[tokio::test]
+async fn hot_seat_rounds_stay_out_of_the_history() {
+    let (mut app, mut io_rx) = start(GameOptions {
+        hot_seat: true,
+        ..GameOptions::default()
+    });
+
+    // The word was typed in by the other player, so it says nothing about how either of them
+    // plays on their own
+    guess(&mut app, "those").await;
+    guess(&mut app, "those").await;
+
+    assert_eq!(app.hot_seat().unwrap().scores, [0, 1]);
+    assert!(recorded(&mut io_rx).is_empty());
+}
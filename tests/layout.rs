@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use common::guess;
+use rustle::app::options::GameOptions;
+use rustle::app::stats::Stats;
+use rustle::app::theme::Theme;
+use rustle::app::ui;
+use rustle::app::words::WordList;
+use rustle::app::App;
+use rustle::inputs::key::Key;
+use tui::backend::TestBackend;
+use tui::style::Color;
+use tui::Terminal;
+
+mod common;
+
+/// An app whose IO events go nowhere, started and with `guess` on the board
+async fn start(options: GameOptions, word: &str) -> App {
+    let mut app = App::new(common::io_sink(), options);
+    app.initialized(Stats::default(), None, 1);
+    guess(&mut app, word).await;
+
+    app
+}
+
+/// The screen drawn at the given size, a line of text per row
+fn draw(app: &App, width: u16, height: u16) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|frame| ui::draw(frame, app)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| buffer.get(x, y).symbol.as_str())
+                .collect()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn large_terminals_get_keys_with_borders() {
+    let app = start(GameOptions::default(), "crane").await;
+    let screen = draw(&app, 80, 40).join("\n");
+
+    assert!(screen.contains("│Q│"), "{}", screen);
+}
+
+#[tokio::test]
+async fn small_terminals_get_a_compact_layout() {
+    let app = start(GameOptions::default(), "crane").await;
+    let screen = draw(&app, 40, 16);
+
+    assert!(
+        screen.iter().any(|line| line.contains(" Q  W  E  R  T ")),
+        "{}",
+        screen.join("\n")
+    );
+    assert!(!screen.join("\n").contains("│Q│"));
+}
+
+#[tokio::test]
+async fn tiny_terminals_are_asked_to_be_bigger() {
+    let app = start(GameOptions::default(), "crane").await;
+    let screen = draw(&app, 30, 10).join("");
+
+    assert!(screen.contains("Please enlarge your window"), "{}", screen);
+    assert!(screen.contains("this is 30x10"), "{}", screen);
+}
+
+#[tokio::test]
+async fn every_size_can_be_drawn() {
+    let long_words = vec![String::from("typewriters")];
+    let games = [
+        GameOptions::default(),
+        GameOptions {
+            boards: 4,
+            hints: true,
+            ..GameOptions::default()
+        },
+        GameOptions {
+            word_length: 11,
+            max_attempts: None,
+            words: Arc::new(WordList::new(long_words.clone(), long_words)),
+            ..GameOptions::default()
+        },
+    ];
+
+    for (options, guess) in games.into_iter().zip(["crane", "crane", "typewriters"]) {
+        let mut app = start(options, guess).await;
+        // The IO events go nowhere, so the hints are worked out here instead
+        if app.options().hints {
+            let question = app.hints_question();
+            let ranking = app.solver().suggestions(&question.0, &question.1);
+            app.hints_ranked(question, 100, ranking);
+        }
+        for width in (1..=90).step_by(11) {
+            for height in (1..=40).step_by(3) {
+                draw(&app, width, height);
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn monochrome_marks_letters_without_colour() {
+    let options = GameOptions {
+        word: Some(String::from("those")),
+        theme: Theme::monochrome(),
+        ..GameOptions::default()
+    };
+    let mut app = start(options, "crane").await;
+    for letter in "ethos".chars() {
+        app.do_action(Key::Char(letter)).await;
+    }
+    app.do_action(Key::Enter).await;
+    app.update_on_tick().await;
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+    terminal.draw(|frame| ui::draw(frame, &app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let screen = draw(&app, 80, 40).join("\n");
+
+    assert!(screen.contains(" c  r  a  n [E]"), "{}", screen);
+    assert!(screen.contains("(E)(T)(H)(O)(S)"), "{}", screen);
+    assert!(screen.contains("Q  W [E] r (T) Y"), "{}", screen);
+    assert!(buffer
+        .content()
+        .iter()
+        .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+}
+
+#[tokio::test]
+async fn the_end_of_the_game_only_offers_the_stats_when_they_are_shown() {
+    let words = vec![String::from("crane")];
+    let only_crane = Arc::new(WordList::new(words.clone(), words));
+
+    let usual = start(
+        GameOptions {
+            words: Arc::clone(&only_crane),
+            ..GameOptions::default()
+        },
+        "crane",
+    )
+    .await;
+    let screen = draw(&usual, 80, 40).join("\n");
+    assert!(screen.contains("Press Enter for statistics"), "{}", screen);
+
+    // Two boards are kept out of the stats, so Enter goes straight to the analysis
+    let mut boards = start(
+        GameOptions {
+            boards: 2,
+            max_attempts: Some(7),
+            words: only_crane,
+            ..GameOptions::default()
+        },
+        "crane",
+    )
+    .await;
+    let screen = draw(&boards, 80, 40).join("\n");
+    assert!(screen.contains("Press Enter for analysis"), "{}", screen);
+    assert!(!screen.contains("statistics"), "{}", screen);
+
+    boards.do_action(Key::Enter).await;
+    let screen = draw(&boards, 80, 40).join("\n");
+    assert!(!screen.contains("Played"), "{}", screen);
+}
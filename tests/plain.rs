@@ -0,0 +1,182 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use rustle::app::options::GameOptions;
+use rustle::app::save::SavedGame;
+use rustle::app::stats::Stats;
+use rustle::app::words::WordList;
+use rustle::app::App;
+use rustle::plain;
+use tokio::sync::Mutex;
+
+mod common;
+
+/// Options for a game of "those"
+fn those() -> GameOptions {
+    GameOptions {
+        word: Some(String::from("those")),
+        ..GameOptions::default()
+    }
+}
+
+/// An app whose IO events go nowhere, started with `saved` as the game left unfinished last time
+fn start(options: GameOptions, saved: Option<SavedGame>) -> Arc<Mutex<App>> {
+    let mut app = App::new(common::io_sink(), options);
+    app.initialized(Stats::default(), saved, 1);
+
+    Arc::new(Mutex::new(app))
+}
+
+/// Everything printed while `input` is played a line at a time
+async fn play(app: &Arc<Mutex<App>>, input: &str) -> String {
+    let mut output = Vec::new();
+    plain::play(app, Cursor::new(input), &mut output)
+        .await
+        .unwrap();
+
+    String::from_utf8(output).unwrap()
+}
+
+#[tokio::test]
+async fn every_letter_of_a_guess_is_described() {
+    let app = start(those(), None);
+    let output = play(&app, "crane\nethos\nthose\n").await;
+
+    assert!(
+        output.contains("Guess the 5 letter word in 6 guesses."),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains(
+            "Guess 1 of 6, CRANE: C absent, R absent, A absent, N absent, E correct\n\
+             Guess 2 of 6, ETHOS: E present, T present, H present, O present, S present\n\
+             Guess 3 of 6, THOSE: T correct, H correct, O correct, S correct, E correct\n\
+             You have won! It took 3 attempts.\n\
+             Played 1, won 100%, current streak 1, best streak 1."
+        ),
+        "{}",
+        output
+    );
+}
+
+#[tokio::test]
+async fn rejected_guesses_say_why() {
+    let app = start(those(), None);
+    let output = play(&app, "cran\ncr4ne\nxxxxx\n\ncrane\n/letters\n").await;
+
+    assert!(output.contains("Not enough letters\n"), "{}", output);
+    assert!(
+        output.contains("Guesses can only have the letters A to Z\n"),
+        "{}",
+        output
+    );
+    assert!(output.contains("Not in word list\n"), "{}", output);
+    assert!(output.contains("Guess 1 of 6, CRANE: "), "{}", output);
+    assert!(
+        output.contains("Correct: E. Absent: A, C, N, R. Not guessed yet: B, D, F,"),
+        "{}",
+        output
+    );
+
+    // Running out of input leaves the game unfinished, to be saved like on the full screen
+    assert_eq!(app.lock().await.state().guesses, vec!["crane"]);
+    assert!(app.lock().await.saved_game().is_some());
+}
+
+#[tokio::test]
+async fn losing_gives_the_word_away() {
+    let app = start(those(), None);
+    let output = play(&app, &"crane\nethos\n".repeat(3)).await;
+
+    assert!(
+        output.ends_with(
+            "Guess 6 of 6, ETHOS: E present, T present, H present, O present, S present\n\
+             You lost. The correct word was \"those\".\n\
+             Played 1, won 0%, current streak 0, best streak 0.\n"
+        ),
+        "{}",
+        output
+    );
+}
+
+#[tokio::test]
+async fn every_board_gets_its_own_line() {
+    let words = vec![String::from("those"), String::from("crane")];
+    let options = GameOptions {
+        boards: 2,
+        puzzle: Some(0),
+        words: Arc::new(WordList::new(words.clone(), words)),
+        ..GameOptions::default()
+    };
+    let app = start(options, None);
+    let solutions: Vec<String> = app
+        .lock()
+        .await
+        .state()
+        .boards
+        .iter()
+        .map(|board| board.solution.clone())
+        .collect();
+    let output = play(&app, &format!("{}\n{}\n", solutions[0], solutions[1])).await;
+
+    assert!(
+        output.contains("Guess all 2 of the 5 letter words at once in 6 guesses."),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains(&format!(
+            "Guess 2 of 6, {}\nBoard 1: solved already\nBoard 2: ",
+            solutions[1].to_uppercase()
+        )),
+        "{}",
+        output
+    );
+}
+
+#[tokio::test]
+async fn survival_runs_go_on_to_the_next_word() {
+    let options = GameOptions {
+        survival: Some(2),
+        ..those()
+    };
+    let app = start(options, None);
+    let output = play(&app, "those\nthose\n").await;
+
+    assert!(
+        output.contains("Solved! Next word. Guess the 5 letter word in 1 guess. 1 guess left"),
+        "{}",
+        output
+    );
+    assert!(
+        output.ends_with("Out of guesses!\nYou solved 2 words using 2 guesses.\n"),
+        "{}",
+        output
+    );
+}
+
+#[tokio::test]
+async fn unfinished_games_can_be_carried_on() {
+    let unfinished = start(those(), None);
+    play(&unfinished, "crane\n").await;
+    let saved = unfinished.lock().await.saved_game();
+
+    let app = start(those(), saved);
+    let output = play(&app, "maybe\ny\nthose\n").await;
+
+    assert!(
+        output.starts_with(
+            "You left a game unfinished after 1 guess. Carry on with it? Type y to carry on or n for a new game.\n\
+             You left a game unfinished after 1 guess."
+        ),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("Guess 1 of 6, CRANE: C absent")
+            && output.contains("Guess 2 of 6, THOSE: T correct"),
+        "{}",
+        output
+    );
+}
@@ -0,0 +1,171 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use rustle::app::daily::{self, SystemClock};
+use rustle::app::options::GameOptions;
+use rustle::app::save::SavedGame;
+use rustle::app::stats::Stats;
+use rustle::app::words::WordList;
+use rustle::app::App;
+use rustle::inputs::key::Key;
+use rustle::io::handler::IoAsyncHandler;
+use rustle::io::{storage, IoEvent};
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc;
+
+/// The data directory is picked with an environment variable, so tests that use it take turns
+static DATA_DIR: Mutex<()> = Mutex::new(());
+
+/// Runs `test` with an empty data directory of its own
+fn with_data_dir(test: impl FnOnce(&Path)) {
+    let _turn = DATA_DIR.lock().unwrap_or_else(PoisonError::into_inner);
+    let dir = tempfile::tempdir().unwrap();
+    std::env::set_var(storage::DATA_DIR_VAR, dir.path());
+
+    test(dir.path());
+}
+
+/// A game started like `main` starts one, picking up whatever was left in the data directory
+fn start(options: GameOptions) -> App {
+    Runtime::new().unwrap().block_on(async {
+        let (io_tx, _io_rx) = mpsc::channel(100);
+        let app = Arc::new(tokio::sync::Mutex::new(App::new(io_tx, options)));
+        IoAsyncHandler::new(Arc::clone(&app))
+            .handle_io_event(IoEvent::Initialize)
+            .await;
+
+        Arc::try_unwrap(app).ok().unwrap().into_inner()
+    })
+}
+
+/// Today's daily puzzle with a guess made, as it would be saved if it were quit
+fn unfinished_daily() -> (GameOptions, SavedGame) {
+    let options = GameOptions {
+        puzzle: Some(daily::todays_puzzle(&SystemClock)),
+        ..GameOptions::default()
+    };
+    let saved = Runtime::new().unwrap().block_on(async {
+        let (io_tx, _io_rx) = mpsc::channel(100);
+        let mut app = App::new(io_tx, options.clone());
+        app.initialized(Stats::default(), None, daily::todays_puzzle(&SystemClock));
+        for key in "crane".chars().map(Key::Char).chain([Key::Enter]) {
+            app.do_action(key).await;
+        }
+
+        app.saved_game().unwrap()
+    });
+
+    (options, saved)
+}
+
+#[test]
+fn todays_puzzle_carries_on_by_itself() {
+    with_data_dir(|_| {
+        let (options, saved) = unfinished_daily();
+        saved.save(&storage::save_path(&options)).unwrap();
+
+        let app = start(options);
+        assert_eq!(app.state().guesses, vec!["crane"]);
+        assert!(app.resume_offer().is_none());
+    });
+}
+
+#[test]
+fn other_games_asked_for_are_played_instead_of_todays_puzzle() {
+    with_data_dir(|_| {
+        let (daily, saved) = unfinished_daily();
+        saved.save(&storage::save_path(&daily)).unwrap();
+
+        let code = start(GameOptions {
+            word: Some(String::from("those")),
+            ..GameOptions::default()
+        });
+        assert!(code.state().guesses.is_empty());
+        assert!(code.resume_offer().is_none());
+        assert_eq!(code.state().boards[0].solution, "those");
+
+        let absurdle = start(GameOptions {
+            absurdle: true,
+            max_attempts: None,
+            ..GameOptions::default()
+        });
+        assert!(absurdle.state().guesses.is_empty());
+        assert!(absurdle.resume_offer().is_none());
+        assert!(absurdle.state().candidates.is_some());
+
+        // The daily puzzle is still there for when it's asked for
+        assert_eq!(
+            SavedGame::load(&storage::save_path(&daily)).unwrap(),
+            Some(saved)
+        );
+    });
+}
+
+#[test]
+fn quitting_another_kind_of_game_keeps_the_save() {
+    with_data_dir(|_| {
+        let (daily, saved) = unfinished_daily();
+        saved.save(&storage::save_path(&daily)).unwrap();
+
+        // Quit partway, the way `main` saves a game on the way out
+        let mut absurdle = start(GameOptions {
+            absurdle: true,
+            max_attempts: None,
+            ..GameOptions::default()
+        });
+        Runtime::new().unwrap().block_on(async {
+            for key in "slate".chars().map(Key::Char).chain([Key::Enter]) {
+                absurdle.do_action(key).await;
+            }
+        });
+        let quit = absurdle.saved_game().unwrap();
+        quit.save(&storage::save_path(absurdle.options())).unwrap();
+
+        assert_eq!(start(daily).state().guesses, vec!["crane"]);
+        let absurdle = start(GameOptions {
+            absurdle: true,
+            max_attempts: None,
+            ..GameOptions::default()
+        });
+        assert_eq!(absurdle.resume_offer(), Some(&quit));
+    });
+}
+
+#[test]
+fn unlimited_games_only_carry_on_as_unlimited_games() {
+    with_data_dir(|_| {
+        let unlimited = GameOptions {
+            max_attempts: None,
+            ..GameOptions::default()
+        };
+        let saved = Runtime::new().unwrap().block_on(async {
+            let (io_tx, _io_rx) = mpsc::channel(100);
+            let mut app = App::new(io_tx, unlimited.clone());
+            app.initialized(Stats::default(), None, 1);
+            let solution = app.state().boards[0].solution.clone();
+            let answers = WordList::builtin().answers().to_vec();
+            let wrong = answers.into_iter().filter(|word| *word != solution).take(8);
+            for word in wrong {
+                for key in word.chars().map(Key::Char).chain([Key::Enter]) {
+                    app.do_action(key).await;
+                }
+            }
+
+            app.saved_game().unwrap()
+        });
+        assert_eq!(saved.guesses.len(), 8);
+        saved.save(&storage::save_path(&unlimited)).unwrap();
+
+        // Even in the usual game's file, as an older build would have saved it, it's not offered
+        // to a game with 6 guesses, where it would be lost already
+        saved
+            .save(&storage::save_path(&GameOptions::default()))
+            .unwrap();
+        let usual = start(GameOptions::default());
+        assert!(usual.resume_offer().is_none());
+        assert!(usual.state().guesses.is_empty());
+        assert_eq!(usual.state().max_attempts, Some(6));
+
+        assert_eq!(start(unlimited).resume_offer(), Some(&saved));
+    });
+}
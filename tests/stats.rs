@@ -0,0 +1,128 @@
+use common::guess;
+use rustle::app::options::GameOptions;
+use rustle::app::save::SavedGame;
+use rustle::app::state::{AppState, GameStatus};
+use rustle::app::stats::{Stats, DISTRIBUTION_LEN};
+use rustle::app::versus::Role;
+use rustle::app::App;
+use rustle::inputs::key::Key;
+
+mod common;
+
+/// Solves a game of "those" on the first guess
+async fn solve(options: GameOptions) -> App {
+    let mut app = common::start(options);
+    guess(&mut app, "those").await;
+
+    assert!(app.state().game_status == GameStatus::Win);
+    app
+}
+
+#[tokio::test]
+async fn only_games_played_the_usual_way_count_towards_the_stats() {
+    let games = [
+        ("random", GameOptions::default(), true),
+        (
+            "daily",
+            GameOptions {
+                puzzle: Some(1),
+                ..GameOptions::default()
+            },
+            true,
+        ),
+        (
+            "hard",
+            GameOptions {
+                hard_mode: true,
+                ..GameOptions::default()
+            },
+            true,
+        ),
+        (
+            "absurdle",
+            GameOptions {
+                absurdle: true,
+                max_attempts: None,
+                ..GameOptions::default()
+            },
+            false,
+        ),
+        (
+            "two boards",
+            GameOptions {
+                boards: 2,
+                max_attempts: Some(7),
+                ..GameOptions::default()
+            },
+            false,
+        ),
+        (
+            "unlimited",
+            GameOptions {
+                max_attempts: None,
+                ..GameOptions::default()
+            },
+            false,
+        ),
+        (
+            "more attempts",
+            GameOptions {
+                max_attempts: Some(8),
+                ..GameOptions::default()
+            },
+            false,
+        ),
+        (
+            "code",
+            GameOptions {
+                word: Some(String::from("those")),
+                ..GameOptions::default()
+            },
+            true,
+        ),
+    ];
+
+    for (name, options, counts) in games {
+        let app = solve(options).await;
+        let played = if counts { 1 } else { 0 };
+
+        assert_eq!(app.counts_towards_stats(), counts, "{}", name);
+        assert_eq!(app.stats().played, played, "{}", name);
+        assert_eq!(app.stats().distribution.len(), DISTRIBUTION_LEN, "{}", name);
+    }
+}
+
+#[tokio::test]
+async fn head_to_head_games_dont_count_towards_the_stats() {
+    let mut app = App::new(common::io_sink(), GameOptions::default());
+    assert!(app.counts_towards_stats());
+
+    app.start_versus(Role::Host, String::from("127.0.0.1:7878"));
+    assert!(!app.counts_towards_stats());
+}
+
+#[tokio::test]
+async fn resumed_games_count_by_how_they_were_played() {
+    // An unlimited game left after 8 guesses, resumed where 6 guesses are the usual
+    let mut unlimited = AppState {
+        max_attempts: None,
+        ..AppState::with_solution("those")
+    };
+    for _ in 0..8 {
+        unlimited.guesses.push(String::from("crane"));
+        unlimited.check_word_status();
+        unlimited.increment_attempt();
+    }
+    let saved = SavedGame::from_state(&unlimited);
+
+    let mut app = App::new(common::io_sink(), GameOptions::default());
+    app.initialized(Stats::default(), saved, 1);
+    app.do_action(Key::Char('y')).await;
+    guess(&mut app, "those").await;
+
+    assert!(app.state().game_status == GameStatus::Win);
+    assert_eq!(app.state().guesses.len(), 9);
+    assert!(!app.counts_towards_stats());
+    assert_eq!(app.stats().played, 0);
+    assert_eq!(app.stats().distribution.len(), DISTRIBUTION_LEN);
+}
@@ -0,0 +1,58 @@
+use common::guess;
+use rustle::app::options::GameOptions;
+use rustle::app::state::GameStatus;
+use rustle::app::App;
+
+mod common;
+
+/// A survival run of "those" with a pool of guesses
+fn start(pool: usize) -> App {
+    common::start(GameOptions {
+        survival: Some(pool),
+        ..GameOptions::default()
+    })
+}
+
+#[tokio::test]
+async fn solved_words_carry_their_spare_guesses_over() {
+    let mut app = start(8);
+
+    guess(&mut app, "crane").await;
+    guess(&mut app, "those").await;
+
+    // The next word starts straight away, with the six guesses it's allowed
+    assert!(app.state().guesses.is_empty());
+    assert!(app.state().game_status == GameStatus::InProgress);
+    assert_eq!(app.state().max_attempts, Some(6));
+    let survival = app.survival().unwrap();
+    assert_eq!((survival.solved, survival.pool), (1, 6));
+
+    for _ in 0..5 {
+        guess(&mut app, "crane").await;
+    }
+    guess(&mut app, "those").await;
+
+    // The guesses were all used up on the last word, even though it was solved
+    assert!(app.state().game_status == GameStatus::Win);
+    let survival = app.survival().unwrap();
+    assert_eq!((survival.solved, survival.used), (2, 8));
+    assert!(survival.is_over());
+}
+
+#[tokio::test]
+async fn missed_words_cost_their_guesses_and_the_last_one_gets_what_is_left() {
+    let mut app = start(8);
+
+    for _ in 0..6 {
+        guess(&mut app, "crane").await;
+    }
+    assert_eq!(app.state().max_attempts, Some(2));
+    assert_eq!(app.survival().unwrap().solved, 0);
+
+    guess(&mut app, "crane").await;
+    guess(&mut app, "crane").await;
+
+    assert!(app.state().game_status == GameStatus::Lose);
+    assert_eq!(app.stats().played, 0);
+    assert!(app.saved_game().is_none());
+}
@@ -0,0 +1,228 @@
+use std::io;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use rustle::app::options::GameOptions;
+use rustle::app::App;
+use rustle::inputs::events::Events;
+use rustle::start_ui;
+use rustle::terminal::{self, Screen, TerminalGuard};
+use tokio::runtime::Runtime;
+
+mod common;
+use tui::backend::{Backend, TestBackend};
+use tui::buffer::Cell;
+use tui::layout::Rect;
+use tui::widgets::Paragraph;
+
+/// The modes a real terminal would be left in
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Modes {
+    raw: bool,
+    alternate_screen: bool,
+    cursor_hidden: bool,
+}
+
+impl Modes {
+    fn restored(self) -> bool {
+        self == Modes::default()
+    }
+}
+
+/// The panic hook is shared by every test, so tests that change it take turns
+static PANIC_HOOK: Mutex<()> = Mutex::new(());
+
+/// Draws into a `TestBackend` and keeps track of the modes it's switched into, which outlive it so
+/// they can be checked once the guard has been dropped
+struct FakeBackend {
+    screen: TestBackend,
+    modes: Arc<Mutex<Modes>>,
+    broken: bool,
+}
+
+impl FakeBackend {
+    fn new(modes: &Arc<Mutex<Modes>>) -> FakeBackend {
+        FakeBackend {
+            screen: TestBackend::new(60, 32),
+            modes: Arc::clone(modes),
+            broken: false,
+        }
+    }
+
+    /// A backend that fails to draw anything, like a terminal that's gone away
+    fn broken(modes: &Arc<Mutex<Modes>>) -> FakeBackend {
+        FakeBackend {
+            broken: true,
+            ..FakeBackend::new(modes)
+        }
+    }
+}
+
+impl Backend for FakeBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if self.broken {
+            return Err(io::Error::other("the terminal went away"));
+        }
+
+        self.screen.draw(content)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.modes.lock().unwrap().cursor_hidden = true;
+        self.screen.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.modes.lock().unwrap().cursor_hidden = false;
+        self.screen.show_cursor()
+    }
+
+    fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+        self.screen.get_cursor()
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.screen.set_cursor(x, y)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.screen.clear()
+    }
+
+    fn size(&self) -> io::Result<Rect> {
+        self.screen.size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.screen.flush()
+    }
+}
+
+impl Screen for FakeBackend {
+    fn set_raw_mode(&mut self, enabled: bool) -> io::Result<()> {
+        self.modes.lock().unwrap().raw = enabled;
+        Ok(())
+    }
+
+    fn set_alternate_screen(&mut self, enabled: bool) -> io::Result<()> {
+        self.modes.lock().unwrap().alternate_screen = enabled;
+        Ok(())
+    }
+}
+
+/// Takes over the terminal, draws a frame, then fails partway through like a `?` would
+fn play_until_an_error(modes: &Arc<Mutex<Modes>>) -> io::Result<()> {
+    let mut terminal = TerminalGuard::new(FakeBackend::new(modes))?;
+    terminal.draw(|frame| frame.render_widget(Paragraph::new("Rustle"), frame.size()))?;
+
+    Err(io::Error::other("the game broke"))
+}
+
+/// A game whose IO events go nowhere
+fn game() -> Arc<tokio::sync::Mutex<App>> {
+    Arc::new(tokio::sync::Mutex::new(App::new(
+        common::io_sink(),
+        GameOptions::default(),
+    )))
+}
+
+/// Nobody pressing anything
+fn idle(wait: Duration) -> Option<rustle::inputs::InputEvent> {
+    std::thread::sleep(wait);
+    None
+}
+
+#[test]
+fn the_terminal_is_taken_over_and_given_back() {
+    let modes = Arc::new(Mutex::new(Modes::default()));
+
+    let terminal = TerminalGuard::new(FakeBackend::new(&modes)).unwrap();
+    assert_eq!(
+        *modes.lock().unwrap(),
+        Modes {
+            raw: true,
+            alternate_screen: true,
+            cursor_hidden: true,
+        }
+    );
+
+    drop(terminal);
+    assert!(modes.lock().unwrap().restored());
+}
+
+#[test]
+fn errors_give_the_terminal_back() {
+    let modes = Arc::new(Mutex::new(Modes::default()));
+
+    assert!(play_until_an_error(&modes).is_err());
+    assert!(modes.lock().unwrap().restored());
+}
+
+#[test]
+fn panics_give_the_terminal_back_before_the_message_is_printed() {
+    let _turn = PANIC_HOOK.lock().unwrap_or_else(PoisonError::into_inner);
+    let modes = Arc::new(Mutex::new(Modes::default()));
+
+    // Stands in for the default hook, noting whether the terminal was back to normal by the time
+    // the message would have been printed
+    let printed = Arc::new(Mutex::new(Vec::new()));
+    let hook_modes = Arc::clone(&modes);
+    let hook_printed = Arc::clone(&printed);
+    std::panic::set_hook(Box::new(move |_| {
+        let restored = hook_modes.lock().unwrap().restored();
+        hook_printed.lock().unwrap().push(restored);
+    }));
+
+    let restore_modes = Arc::clone(&modes);
+    terminal::set_panic_hook(move || {
+        let _ = terminal::restore(&mut FakeBackend::new(&restore_modes));
+    });
+
+    let result = std::panic::catch_unwind(|| {
+        let _terminal = TerminalGuard::new(FakeBackend::new(&modes)).unwrap();
+        panic!("the game broke");
+    });
+    let _ = std::panic::take_hook();
+
+    assert!(result.is_err());
+    assert_eq!(*printed.lock().unwrap(), vec![true]);
+    assert!(modes.lock().unwrap().restored());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn errors_while_playing_give_the_terminal_back() {
+    let modes = Arc::new(Mutex::new(Modes::default()));
+    let events = Events::from_source(Duration::from_millis(10), idle);
+
+    let result = start_ui(&game(), FakeBackend::broken(&modes), events).await;
+    assert!(result.is_err());
+    assert!(modes.lock().unwrap().restored());
+}
+
+#[test]
+fn panics_in_other_threads_stop_the_game() {
+    let _turn = PANIC_HOOK.lock().unwrap_or_else(PoisonError::into_inner);
+    let modes = Arc::new(Mutex::new(Modes::default()));
+
+    let result = Runtime::new().unwrap().block_on(async {
+        // The keyboard stops working, the way the events thread would panic if the terminal
+        // couldn't be read. The hook stands in for the one `main` sets.
+        let events =
+            Events::from_source(Duration::from_millis(10), |_| panic!("the keyboard broke"));
+        let interrupt = events.interrupt();
+        std::panic::set_hook(Box::new(move |_| interrupt.interrupt()));
+
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            start_ui(&game(), FakeBackend::new(&modes), events),
+        )
+        .await
+    });
+    let _ = std::panic::take_hook();
+
+    assert!(result.expect("the game carried on").is_err());
+    assert!(modes.lock().unwrap().restored());
+}
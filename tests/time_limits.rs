@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use common::guess;
+use rustle::app::options::{GameOptions, TimeLimit};
+use rustle::app::state::GameStatus;
+use rustle::app::App;
+
+mod common;
+
+/// A game of "those" against the clock
+fn start(time_limit: TimeLimit) -> App {
+    common::start(GameOptions {
+        time_limit: Some(time_limit),
+        ..GameOptions::default()
+    })
+}
+
+#[tokio::test]
+async fn running_out_of_time_loses_the_game() {
+    let mut app = start(TimeLimit::Countdown(Duration::from_millis(300)));
+
+    guess(&mut app, "crane").await;
+    assert!(app.state().game_status == GameStatus::InProgress);
+    assert!(app.time_left().unwrap() > Duration::ZERO);
+
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    app.update_on_tick().await;
+
+    assert!(app.state().game_status == GameStatus::Lose);
+    assert_eq!(app.time_left(), Some(Duration::ZERO));
+    // Racing the clock is a different game, so it's kept out of the stats
+    assert!(!app.counts_towards_stats());
+    assert_eq!(app.stats().played, 0);
+}
+
+#[tokio::test]
+async fn speedruns_go_on_to_the_next_word_until_the_time_is_up() {
+    let limit = Duration::from_millis(300);
+    let mut app = start(TimeLimit::Speedrun(limit));
+
+    guess(&mut app, "those").await;
+    guess(&mut app, "crane").await;
+    guess(&mut app, "those").await;
+
+    let speedrun = app.speedrun().unwrap();
+    assert_eq!(speedrun.solved, 2);
+    assert!(app.state().guesses.is_empty());
+    assert!(app.state().game_status == GameStatus::InProgress);
+
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    app.update_on_tick().await;
+
+    assert!(app.state().game_status == GameStatus::Lose);
+    assert!(app.speedrun().unwrap().is_new_best());
+    // Only the run counts, not the words in it
+    assert_eq!(app.stats().speedrun_best(limit), Some(2));
+    assert_eq!(app.stats().played, 0);
+    assert!(app.saved_game().is_none());
+}